@@ -3,3 +3,4 @@
 
 pub mod requests;
 pub mod responses;
+pub mod validation;
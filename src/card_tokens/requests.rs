@@ -1,57 +1,171 @@
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
 
+use crate::card_tokens::validation;
 use crate::common_types::{Cardholder, PersonalIdentification};
+use crate::errors::CreditCardError;
+use crate::payments::requests::PaymentMethodId;
+use crate::sensitive::SensitiveString;
 
-/// Options used to create a Card Token.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct CardTokenOptions<'a> {
-    pub(crate) card_number: &'a str,
-    pub(crate) expiration_month: &'a str,
-    pub(crate) expiration_year: &'a str,
-    pub(crate) security_code: &'a str,
-    pub(crate) cardholder: Cardholder,
+/// Options used to create a Card Token, either from the card data or from a card saved to a
+/// customer.
+///
+/// The card number and security code are wiped from memory once dropped, and redacted from
+/// `Debug` output.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CardTokenOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) card_number: Option<SensitiveString>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) expiration_month: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) expiration_year: Option<String>,
+    pub(crate) security_code: SensitiveString,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) cardholder: Option<Cardholder>,
+
+    /// ID of a card saved through
+    /// [`MercadoPagoSDK::save_customer_card`](crate::MercadoPagoSDK::save_customer_card).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) card_id: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) public_key: Option<String>,
 }
 
-impl<'a> CardTokenOptions<'a> {
+impl CardTokenOptions {
     pub fn new(
-        card_number: &'a str,
-        card_expiration_month: &'a str,
-        card_expiration_year: &'a str,
-        security_code: &'a str,
+        card_number: &str,
+        card_expiration_month: &str,
+        card_expiration_year: &str,
+        security_code: &str,
         cardholder_name: String,
         cardholder_personal_id: PersonalIdentification,
-    ) -> CardTokenOptions<'a> {
+    ) -> CardTokenOptions {
         Self {
-            card_number,
-            expiration_month: card_expiration_month,
-            expiration_year: card_expiration_year,
-            security_code,
-            cardholder: Cardholder {
+            card_number: Some(card_number.into()),
+            expiration_month: Some(card_expiration_month.to_string()),
+            expiration_year: Some(card_expiration_year.to_string()),
+            security_code: security_code.into(),
+            cardholder: Some(Cardholder {
                 name: cardholder_name,
                 identification: cardholder_personal_id,
-            },
+            }),
 
+            card_id: None,
             public_key: None,
         }
     }
+
+    /// Creates a token for a card saved to a customer, which only needs its security code.
+    pub fn saved_card(card_id: &str, security_code: &str) -> CardTokenOptions {
+        Self {
+            card_number: None,
+            expiration_month: None,
+            expiration_year: None,
+            security_code: security_code.into(),
+            cardholder: None,
+
+            card_id: Some(card_id.to_string()),
+            public_key: None,
+        }
+    }
+
+    /// Validates the card offline, without contacting MercadoPago, returning its brand. See
+    /// [`validation::validate_card`].
+    ///
+    /// Saved cards are only known by MercadoPago, so they are never rejected and have no brand.
+    pub fn validate(&self) -> Result<Option<PaymentMethodId>, CreditCardError> {
+        match (
+            &self.card_number,
+            &self.expiration_month,
+            &self.expiration_year,
+        ) {
+            (Some(number), Some(month), Some(year)) => {
+                validation::validate_card(number.expose(), month, year, self.security_code.expose())
+                    .map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::payments::requests::DocumentType;
 
     #[test]
     fn t_token_serialization() {
-        #[test]
-        fn t_token_response() {
-            let payload = serde_json::from_slice::<CardTokenOptions>(include_bytes!(
+        let payload = CardTokenOptions::new(
+            "4074090000000004",
+            "11",
+            "2025",
+            "123",
+            "APRO".to_string(),
+            PersonalIdentification::new(DocumentType::CPF, "19119119100").unwrap(),
+        );
+
+        assert_eq!(
+            serde_json::to_value(payload).unwrap(),
+            serde_json::from_slice::<serde_json::Value>(include_bytes!(
                 "../../tests/assets/card_tokens_create_request.json"
             ))
-            .unwrap();
-            println!("{:?}", payload);
-        }
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn t_debug_is_redacted() {
+        let payload = CardTokenOptions::new(
+            "4074090000000004",
+            "11",
+            "2025",
+            "123",
+            "APRO".to_string(),
+            PersonalIdentification::new(DocumentType::CPF, "19119119100").unwrap(),
+        );
+        let debug = format!("{:?}", payload);
+
+        assert!(!debug.contains("4074090000000004"));
+        assert!(!debug.contains("\"123\""));
+        assert!(debug.contains("407409******0004"));
+    }
+
+    #[test]
+    fn t_saved_card_token_serialization() {
+        let payload = CardTokenOptions::saved_card("9016637386", "123");
+
+        assert_eq!(
+            serde_json::to_value(payload).unwrap(),
+            serde_json::json!({"security_code": "123", "card_id": "9016637386"})
+        );
+    }
+
+    #[test]
+    fn t_validate() {
+        let cardholder = PersonalIdentification::new(DocumentType::CPF, "12345678909").unwrap();
+        let valid = CardTokenOptions::new(
+            "5031433215406351",
+            "11",
+            "2099",
+            "123",
+            "APRO".to_string(),
+            cardholder.clone(),
+        );
+        let typo = CardTokenOptions::new(
+            "5031433215406352",
+            "11",
+            "2099",
+            "123",
+            "APRO".to_string(),
+            cardholder,
+        );
+
+        assert_eq!(valid.validate(), Ok(Some(PaymentMethodId::Master)));
+        assert_eq!(typo.validate(), Err(CreditCardError::InvalidChecksum));
+        assert_eq!(
+            CardTokenOptions::saved_card("9016637386", "123").validate(),
+            Ok(None)
+        );
     }
 }
@@ -0,0 +1,334 @@
+//! Client-side validation of card data, catching typos before a token is requested.
+//!
+//! Cards are checked with the Luhn algorithm, their brand is detected from the BIN (first digits)
+//! and their length, expiration date and security code are checked against the brand rules.
+
+use time::{Date, OffsetDateTime};
+
+use crate::errors::CreditCardError;
+use crate::payments::requests::PaymentMethodId;
+
+/// BIN ranges of each brand, as inclusive ranges of prefixes with the same number of digits.
+///
+/// Brands issued inside other brands' ranges, such as Elo inside Visa's `4`, come first.
+const BIN_RANGES: &[(PaymentMethodId, &[(u32, u32)])] = &[
+    (
+        PaymentMethodId::Elo,
+        &[
+            (401178, 401179),
+            (431274, 431274),
+            (438935, 438935),
+            (451416, 451416),
+            (457393, 457393),
+            (457631, 457632),
+            (504175, 504175),
+            (506699, 506778),
+            (509000, 509999),
+            (627780, 627780),
+            (636297, 636297),
+            (636368, 636368),
+            (650031, 650033),
+            (650035, 650051),
+            (650405, 650439),
+            (650485, 650538),
+            (650541, 650598),
+            (650700, 650718),
+            (650720, 650727),
+            (650901, 650978),
+            (651652, 651679),
+            (655000, 655019),
+            (655021, 655058),
+        ],
+    ),
+    (
+        PaymentMethodId::Hipercard,
+        &[
+            (384100, 384100),
+            (384140, 384140),
+            (384160, 384160),
+            (606282, 606282),
+            (637095, 637095),
+            (637568, 637568),
+            (637599, 637599),
+            (637609, 637609),
+            (637612, 637612),
+        ],
+    ),
+    (PaymentMethodId::Naranja, &[(589562, 589562)]),
+    (
+        PaymentMethodId::Cabal,
+        &[
+            (589657, 589657),
+            (603522, 603522),
+            (604201, 604400),
+            (627170, 627170),
+        ],
+    ),
+    (PaymentMethodId::Amex, &[(34, 34), (37, 37)]),
+    (PaymentMethodId::Diners, &[(300, 305), (36, 36), (38, 38)]),
+    // MercadoPago reads any other card starting with 5 as Mastercard, such as its test cards.
+    (PaymentMethodId::Master, &[(5, 5), (2221, 2720)]),
+    (PaymentMethodId::Visa, &[(4, 4)]),
+];
+
+/// Detects the brand of a card from its first digits. Spaces and dashes are ignored.
+pub fn detect_brand(card_number: &str) -> Option<PaymentMethodId> {
+    let digits = strip_separators(card_number);
+
+    BIN_RANGES
+        .iter()
+        .find(|(_, ranges)| {
+            ranges.iter().any(|(start, end)| {
+                let len = start.to_string().len();
+                digits
+                    .get(..len)
+                    .and_then(|prefix| prefix.parse::<u32>().ok())
+                    .is_some_and(|prefix| (*start..=*end).contains(&prefix))
+            })
+        })
+        .map(|(brand, _)| brand.clone())
+}
+
+/// Checks the last digit of `card_number` with the Luhn algorithm. Spaces and dashes are ignored.
+pub fn is_valid_luhn(card_number: &str) -> bool {
+    let digits = strip_separators(card_number);
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return false;
+    }
+
+    let sum: u32 = digits
+        .chars()
+        .rev()
+        .filter_map(|c| c.to_digit(10))
+        .enumerate()
+        .map(|(i, d)| match (i % 2, d * 2) {
+            (0, _) => d,
+            (_, doubled) if doubled > 9 => doubled - 9,
+            (_, doubled) => doubled,
+        })
+        .sum();
+
+    sum.is_multiple_of(10)
+}
+
+/// Validates a card against today's date, returning its brand.
+///
+/// `expiration_year` may have 2 or 4 digits.
+pub fn validate_card(
+    card_number: &str,
+    expiration_month: &str,
+    expiration_year: &str,
+    security_code: &str,
+) -> Result<PaymentMethodId, CreditCardError> {
+    validate_card_at(
+        card_number,
+        expiration_month,
+        expiration_year,
+        security_code,
+        OffsetDateTime::now_utc().date(),
+    )
+}
+
+/// Same as [`validate_card`], with the expiration checked against `today`.
+pub fn validate_card_at(
+    card_number: &str,
+    expiration_month: &str,
+    expiration_year: &str,
+    security_code: &str,
+    today: Date,
+) -> Result<PaymentMethodId, CreditCardError> {
+    let digits = strip_separators(card_number);
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(CreditCardError::MalformedNumber);
+    }
+
+    let brand = detect_brand(&digits).ok_or(CreditCardError::UnknownBrand)?;
+    if !number_lengths(&brand).contains(&digits.len()) {
+        return Err(CreditCardError::InvalidLength {
+            brand,
+            length: digits.len(),
+        });
+    }
+
+    if !is_valid_luhn(&digits) {
+        return Err(CreditCardError::InvalidChecksum);
+    }
+
+    validate_expiration(expiration_month, expiration_year, today)?;
+    validate_security_code(&brand, security_code)?;
+
+    Ok(brand)
+}
+
+/// Checks the expiration date is valid and not past. Cards expire after the last day of their
+/// month.
+pub fn validate_expiration(
+    expiration_month: &str,
+    expiration_year: &str,
+    today: Date,
+) -> Result<(), CreditCardError> {
+    let month = expiration_month
+        .trim()
+        .parse::<u8>()
+        .ok()
+        .filter(|m| (1..=12).contains(m))
+        .ok_or_else(|| CreditCardError::InvalidExpiration(expiration_month.to_string()))?;
+
+    let year = match expiration_year.trim() {
+        y if y.len() == 2 || y.len() == 4 => y.parse::<i32>().ok(),
+        _ => None,
+    }
+    .map(|y| if y < 100 { 2000 + y } else { y })
+    .ok_or_else(|| CreditCardError::InvalidExpiration(expiration_year.to_string()))?;
+
+    if (year, month) < (today.year(), today.month() as u8) {
+        return Err(CreditCardError::Expired { month, year });
+    }
+
+    Ok(())
+}
+
+/// Checks the security code has as many digits as required by `brand`.
+pub fn validate_security_code(
+    brand: &PaymentMethodId,
+    security_code: &str,
+) -> Result<(), CreditCardError> {
+    let expected = security_code_length(brand);
+
+    if security_code.len() != expected || !security_code.chars().all(|c| c.is_ascii_digit()) {
+        return Err(CreditCardError::InvalidSecurityCode {
+            brand: brand.clone(),
+            expected,
+        });
+    }
+
+    Ok(())
+}
+
+fn number_lengths(brand: &PaymentMethodId) -> &'static [usize] {
+    match brand {
+        PaymentMethodId::Amex => &[15],
+        PaymentMethodId::Diners => &[14],
+        PaymentMethodId::Visa => &[13, 16, 19],
+        PaymentMethodId::Hipercard => &[13, 16, 19],
+        _ => &[16],
+    }
+}
+
+fn security_code_length(brand: &PaymentMethodId) -> usize {
+    match brand {
+        PaymentMethodId::Amex => 4,
+        _ => 3,
+    }
+}
+
+fn strip_separators(card_number: &str) -> String {
+    card_number
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '-'))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+
+    use super::*;
+
+    const TODAY: Date = date!(2026 - 10 - 18);
+
+    #[test]
+    fn t_detect_brand() {
+        assert_eq!(
+            detect_brand("4509 9535 6623 3704"),
+            Some(PaymentMethodId::Visa)
+        );
+        assert_eq!(
+            detect_brand("5031433215406351"),
+            Some(PaymentMethodId::Master)
+        );
+        assert_eq!(
+            detect_brand("2221000000000009"),
+            Some(PaymentMethodId::Master)
+        );
+        assert_eq!(detect_brand("371180303257522"), Some(PaymentMethodId::Amex));
+        assert_eq!(detect_brand("5067224275805500"), Some(PaymentMethodId::Elo));
+        assert_eq!(detect_brand("4011780000000000"), Some(PaymentMethodId::Elo));
+        assert_eq!(
+            detect_brand("6062826786276634"),
+            Some(PaymentMethodId::Hipercard)
+        );
+        assert_eq!(
+            detect_brand("5895627823453005"),
+            Some(PaymentMethodId::Naranja)
+        );
+        assert_eq!(
+            detect_brand("6042012045809847"),
+            Some(PaymentMethodId::Cabal)
+        );
+        assert_eq!(detect_brand("9999999999999999"), None);
+    }
+
+    #[test]
+    fn t_luhn() {
+        assert!(is_valid_luhn("4509953566233704"));
+        assert!(is_valid_luhn("5031-4332-1540-6351"));
+        assert!(!is_valid_luhn("4509953566233705"));
+        assert!(!is_valid_luhn("4509a53566233704"));
+    }
+
+    #[test]
+    fn t_validate_card() {
+        assert_eq!(
+            validate_card_at("5031 4332 1540 6351", "11", "2030", "123", TODAY),
+            Ok(PaymentMethodId::Master)
+        );
+        assert_eq!(
+            validate_card_at("371180303257522", "10", "26", "1234", TODAY),
+            Ok(PaymentMethodId::Amex)
+        );
+
+        assert_eq!(
+            validate_card_at("1234", "11", "2030", "123", TODAY),
+            Err(CreditCardError::UnknownBrand)
+        );
+        assert_eq!(
+            validate_card_at("45099535662337", "11", "2030", "123", TODAY),
+            Err(CreditCardError::InvalidLength {
+                brand: PaymentMethodId::Visa,
+                length: 14
+            })
+        );
+        assert_eq!(
+            validate_card_at("4509953566233705", "11", "2030", "123", TODAY),
+            Err(CreditCardError::InvalidChecksum)
+        );
+        assert_eq!(
+            validate_card_at("371180303257522", "11", "2030", "123", TODAY),
+            Err(CreditCardError::InvalidSecurityCode {
+                brand: PaymentMethodId::Amex,
+                expected: 4
+            })
+        );
+    }
+
+    #[test]
+    fn t_expiration() {
+        assert!(validate_expiration("10", "2026", TODAY).is_ok());
+        assert_eq!(
+            validate_expiration("09", "2026", TODAY),
+            Err(CreditCardError::Expired {
+                month: 9,
+                year: 2026
+            })
+        );
+        assert_eq!(
+            validate_expiration("13", "2030", TODAY),
+            Err(CreditCardError::InvalidExpiration("13".to_string()))
+        );
+        assert_eq!(
+            validate_expiration("11", "203", TODAY),
+            Err(CreditCardError::InvalidExpiration("203".to_string()))
+        );
+    }
+}
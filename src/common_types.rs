@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 
 use crate::errors::ValidationError;
-use crate::helpers::option_stringify;
+use crate::helpers::deserialize_option_string_from_number;
+use crate::identification_types::validation;
 use crate::payments::requests::DocumentType;
 use crate::SDKError;
 
@@ -75,8 +76,11 @@ pub struct ExcludedPaymentType {
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Item {
     pub title: String,
+    #[serde(default)]
     pub description: String,
+    #[serde(deserialize_with = "serde_aux::field_attributes::deserialize_number_from_string")]
     pub quantity: i32,
+    #[serde(deserialize_with = "serde_aux::field_attributes::deserialize_number_from_string")]
     pub unit_price: f64,
 
     /// Item ID/sku, identified in your platform.
@@ -121,6 +125,7 @@ pub struct Shipments {
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Address {
+    #[serde(default, deserialize_with = "deserialize_option_string_from_number")]
     pub zip_code: Option<String>,
     pub state_name: Option<String>,
     pub city_name: Option<String>,
@@ -141,29 +146,30 @@ pub struct CheckoutProPayer {
 }
 
 impl CheckoutProPayer {
+    /// Whether the payer has an email and a valid personal identification.
     pub fn validate(&self) -> bool {
-        if self.email.is_none()
-            || self.identification.number.is_none()
-            || self.identification.document_type.is_none()
-        {
-            return false;
+        match (
+            &self.email,
+            &self.identification.document_type,
+            &self.identification.number,
+        ) {
+            (Some(_), Some(document_type), Some(number)) => {
+                validation::validate(document_type, number).is_ok()
+            }
+            _ => false,
         }
-        true
     }
 
-    pub fn standard_payer<II>(
+    pub fn standard_payer<T: ToString>(
         email: String,
         document_type: DocumentType,
-        document_number: II,
-    ) -> Self
-    where
-        II: Into<Option<i64>>,
-    {
+        document_number: T,
+    ) -> Self {
         Self {
             email: Some(email),
             identification: PersonalIdentification {
                 document_type: Some(document_type),
-                number: document_number.into(),
+                number: Some(document_number.to_string()),
             },
 
             name: None,
@@ -173,19 +179,16 @@ impl CheckoutProPayer {
         }
     }
 
-    pub fn minimal_payer<II>(
+    pub fn minimal_payer<T: ToString>(
         email: String,
         document_type: DocumentType,
-        document_number: II,
-    ) -> Self
-    where
-        II: Into<Option<i64>>,
-    {
+        document_number: T,
+    ) -> Self {
         Self {
             email: Some(email),
             identification: PersonalIdentification {
                 document_type: Some(document_type),
-                number: document_number.into(),
+                number: Some(document_number.to_string()),
             },
 
             name: None,
@@ -201,32 +204,44 @@ impl CheckoutProPayer {
 pub struct PersonalIdentification {
     #[serde(rename = "type")]
     pub document_type: Option<DocumentType>,
-    #[serde(
-        default,
-        serialize_with = "option_stringify",
-        deserialize_with = "serde_aux::field_attributes::deserialize_option_number_from_string"
-    )]
-    pub number: Option<i64>,
+    /// Kept as sent, since documents such as CUIT and RUT are formatted with dashes and may have
+    /// letters.
+    #[serde(default, deserialize_with = "deserialize_option_string_from_number")]
+    pub number: Option<String>,
 }
 
 impl PersonalIdentification {
-    pub fn new(document_type: DocumentType, document_number: i64) -> Self {
-        Self {
+    /// Fails with [`ValidationError::Identification`] when the check digits of CPF, CNPJ, CUIT,
+    /// CUIL and RUT numbers don't match. See [`validation`].
+    pub fn new<T: ToString>(
+        document_type: DocumentType,
+        document_number: T,
+    ) -> Result<Self, ValidationError> {
+        let number = document_number.to_string();
+        validation::validate(&document_type, &number)?;
+
+        Ok(Self {
             document_type: Some(document_type),
-            number: Some(document_number),
-        }
+            number: Some(number),
+        })
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Card {
+    #[serde(deserialize_with = "serde_aux::field_attributes::deserialize_string_from_number")]
     pub first_six_digits: String,
+    #[serde(deserialize_with = "serde_aux::field_attributes::deserialize_string_from_number")]
     pub last_four_digits: String,
     pub expiration_month: i64,
     pub expiration_year: i64,
 
-    pub card_number_length: i64,
-    pub security_code_length: i64,
+    /// Not present on cards attached to payments.
+    #[serde(default)]
+    pub card_number_length: Option<i64>,
+    /// Not present on cards attached to payments.
+    #[serde(default)]
+    pub security_code_length: Option<i64>,
 
     pub cardholder: Cardholder,
 
@@ -234,8 +249,9 @@ pub struct Card {
     pub date_created: time::OffsetDateTime,
     #[serde(with = "time::serde::rfc3339")]
     pub date_last_updated: time::OffsetDateTime,
-    #[serde(with = "time::serde::rfc3339")]
-    pub date_due: time::OffsetDateTime,
+    /// Only present on card tokens.
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub date_due: Option<time::OffsetDateTime>,
 }
 
 /// Information of the Credit/Debit Card owner.
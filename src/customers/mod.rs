@@ -0,0 +1,6 @@
+//! Customers save buyers and their cards, enabling one-click checkouts with saved cards.
+//!
+//! Official API page: https://www.mercadopago.com.br/developers/en/reference/customers/_customers/post
+
+pub mod requests;
+pub mod responses;
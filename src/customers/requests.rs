@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common_types::{Address, PersonalIdentification, Phone};
+
+/// Payload of [`MercadoPagoSDK::create_customer`](crate::MercadoPagoSDK::create_customer).
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateCustomerPayload {
+    /// Required. Only one customer can exist with the same email.
+    pub email: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone: Option<Phone>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identification: Option<PersonalIdentification>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+
+    /// Date the buyer registered on your platform.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_registered: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
+}
+
+impl CreateCustomerPayload {
+    pub fn new<T: ToString>(email: T) -> Self {
+        Self {
+            email: email.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Fields that can be changed on an existing customer, through
+/// [`MercadoPagoSDK::update_customer`](crate::MercadoPagoSDK::update_customer).
+///
+/// Only the fields that are set will be sent.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpdateCustomerPayload {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone: Option<Phone>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identification: Option<PersonalIdentification>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// ID of the saved card used by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_card: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// Filters for [`MercadoPagoSDK::search_customers`](crate::MercadoPagoSDK::search_customers),
+/// sent as query parameters.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomerSearchOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) email: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) offset: Option<u32>,
+}
+
+impl CustomerSearchOptions {
+    /// Creates blank filters, which match every customer.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_email<T: ToString>(mut self, email: T) -> Self {
+        self.email = Some(email.to_string());
+        self
+    }
+
+    /// Sets the page to be returned. MercadoPago defaults to a `limit` of 10.
+    pub fn set_pagination(mut self, limit: u32, offset: u32) -> Self {
+        self.limit = Some(limit);
+        self.offset = Some(offset);
+        self
+    }
+}
+
+/// Payload of [`MercadoPagoSDK::save_customer_card`](crate::MercadoPagoSDK::save_customer_card).
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateCustomerCardPayload {
+    /// `card_token_id` of a
+    /// [`CardTokenResponse`](crate::card_tokens::responses::CardTokenResponse).
+    pub token: String,
+}
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common_types::{Address, Card, PersonalIdentification, Phone};
+use crate::payments::responses::Paging;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomerResponse {
+    /// Autogenerated unique ID that identifies the customer.
+    pub id: String,
+    pub email: String,
+
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub phone: Option<Phone>,
+    pub identification: Option<PersonalIdentification>,
+    pub address: Option<Address>,
+    pub description: Option<String>,
+
+    /// ID of the saved card used by default.
+    pub default_card: Option<String>,
+
+    pub date_registered: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub date_created: time::OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub date_last_updated: time::OffsetDateTime,
+
+    pub metadata: Option<serde_json::Value>,
+    /// Cards saved to this customer.
+    #[serde(default)]
+    pub cards: Vec<CustomerCard>,
+    #[serde(default)]
+    pub live_mode: bool,
+}
+
+/// A card saved to a customer. Payments with it need a token from
+/// [`CardTokenOptions::saved_card`](crate::card_tokens::requests::CardTokenOptions::saved_card).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomerCard {
+    pub id: String,
+    pub customer_id: String,
+
+    #[serde(flatten)]
+    pub card: Card,
+
+    pub payment_method: Option<CardPaymentMethod>,
+    pub issuer: Option<CardIssuer>,
+    pub security_code: Option<SecurityCode>,
+
+    #[serde(default)]
+    pub live_mode: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CardPaymentMethod {
+    /// Brand of the card, such as `visa` or `master`.
+    pub id: String,
+    pub name: String,
+    pub payment_type_id: String,
+    pub thumbnail: Option<String>,
+    pub secure_thumbnail: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CardIssuer {
+    pub id: i64,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SecurityCode {
+    pub length: i64,
+    /// Where the security code is printed, such as `back` or `front`.
+    pub card_location: String,
+}
+
+/// Result of [`MercadoPagoSDK::search_customers`](crate::MercadoPagoSDK::search_customers).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomerSearchResponse {
+    pub paging: Paging,
+    pub results: Vec<CustomerResponse>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_deserialization() {
+        let response = serde_json::from_slice::<CustomerResponse>(include_bytes!(
+            "../../tests/assets/customer_response.json"
+        ));
+
+        assert!(response.is_ok());
+    }
+
+    #[test]
+    fn t_card_deserialization() {
+        let card = serde_json::from_slice::<CustomerCard>(include_bytes!(
+            "../../tests/assets/customer_card_response.json"
+        ))
+        .unwrap();
+
+        assert_eq!(card.card.last_four_digits, "6351");
+        assert_eq!(card.security_code.unwrap().length, 3);
+    }
+}
@@ -1,6 +1,11 @@
-use serde::Deserialize;
+use std::fmt;
+
+use serde::{Deserialize, Deserializer};
+use serde_aux::prelude::*;
 use thiserror::Error;
 
+use crate::payments::requests::PaymentMethodId;
+
 #[derive(Error, Debug)]
 pub enum SDKError {
     #[error("{0}")]
@@ -12,29 +17,367 @@ pub enum SDKError {
     #[error(transparent)]
     ValidationError(#[from] ValidationError),
 
+    #[error(transparent)]
+    WebhookError(#[from] WebhookError),
+
+    /// MercadoPago rejected the request.
+    #[error("MercadoPago API error (HTTP {status}): {message}")]
+    Api {
+        /// HTTP status code of the response.
+        status: u16,
+        message: String,
+        /// Short error identifier, such as `bad_request` or `not_found`.
+        error: String,
+        /// Every reason MercadoPago gave for rejecting the request.
+        causes: Vec<ErrorCause>,
+    },
+
+    /// The response could not be deserialized into the expected type.
+    #[error("Failed to deserialize response at `{path}`: {source}")]
+    DeserializationError {
+        /// Path of the field that failed to deserialize, such as `card.expiration_month`.
+        path: String,
+        source: serde_json::Error,
+        /// Raw response body.
+        body: String,
+    },
+
     #[error("Something wrong happened.")]
     GenericError,
 }
 
-#[derive(Error, Debug)]
-pub enum CreditCardError {}
+/// Reasons for rejecting card data before requesting a token. See
+/// [`crate::card_tokens::validation`].
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum CreditCardError {
+    #[error("Card number must only have digits.")]
+    MalformedNumber,
+
+    #[error("Card brand could not be detected from its BIN.")]
+    UnknownBrand,
+
+    #[error("{brand} cards can't have {length} digits.")]
+    InvalidLength {
+        brand: PaymentMethodId,
+        length: usize,
+    },
+
+    #[error("Card number failed the Luhn check.")]
+    InvalidChecksum,
+
+    #[error("Invalid expiration date: {0}")]
+    InvalidExpiration(String),
+
+    #[error("Card expired on {month:02}/{year}.")]
+    Expired { month: u8, year: i32 },
+
+    #[error("{brand} security codes have {expected} digits.")]
+    InvalidSecurityCode {
+        brand: PaymentMethodId,
+        expected: usize,
+    },
+}
+
+/// Reasons for rejecting a webhook notification.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum WebhookError {
+    #[error("Malformed x-signature header: {0}")]
+    MalformedSignature(String),
+
+    #[error("Webhook notification has no x-signature header.")]
+    MissingSignature,
+
+    #[error("Webhook signature does not match.")]
+    InvalidSignature,
+
+    #[error("Malformed IPN notification: {0}")]
+    MalformedNotification(String),
+
+    #[error("Webhook topic `{0}` can't be resolved.")]
+    UnsupportedTopic(String),
+
+    #[error("Webhook resource ID `{0}` is not valid for its topic.")]
+    InvalidResourceId(String),
+
+    #[error("Webhook timestamp {timestamp} is outside the tolerance window.")]
+    ExpiredTimestamp {
+        /// Unix timestamp, in seconds, sent on the `x-signature` header.
+        timestamp: i64,
+    },
+}
 
 #[derive(Error, Debug)]
 pub enum ValidationError {
     #[error("Item validation error: {0}")]
     ItemError(String),
+
+    #[error("Payer validation error: {0}")]
+    PayerError(String),
+
+    #[error("Payment validation error: {0}")]
+    PaymentError(String),
+
+    #[error("Idempotency key validation error: {0}")]
+    IdempotencyKeyError(String),
+
+    #[error("Identification validation error: {0}")]
+    Identification(String),
+
+    #[error("Credit card validation error: {0}")]
+    CreditCard(#[from] CreditCardError),
+}
+
+#[cfg(any(feature = "axum", feature = "actix-web"))]
+impl WebhookError {
+    /// Status answered by webhook receivers when rejecting a notification.
+    pub(crate) fn http_status(&self) -> u16 {
+        match self {
+            WebhookError::MissingSignature
+            | WebhookError::InvalidSignature
+            | WebhookError::ExpiredTimestamp { .. } => 401,
+            _ => 400,
+        }
+    }
 }
 
-#[derive(Debug, Deserialize)]
-pub(crate) struct ApiError {
-    message: String,
-    status: i32,
-    error: String,
-    cause: Option<Vec<ErrorCause>>,
+/// Error body returned by MercadoPago when a request fails.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ApiError {
+    #[serde(default)]
+    pub message: String,
+    #[serde(default)]
+    pub status: i32,
+    #[serde(default)]
+    pub error: String,
+    #[serde(default)]
+    pub cause: Option<Vec<ErrorCause>>,
 }
 
-#[derive(Debug, Deserialize)]
-pub(crate) struct ErrorCause {
+impl ApiError {
+    pub(crate) fn into_sdk_error(self, status: u16) -> SDKError {
+        SDKError::Api {
+            status,
+            message: self.message,
+            error: self.error,
+            causes: self.cause.unwrap_or_default(),
+        }
+    }
+}
+
+/// A single reason for MercadoPago rejecting a request.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ErrorCause {
+    #[serde(default)]
     pub description: String,
-    pub code: String,
+    pub code: ErrorCauseCode,
+}
+
+macro_rules! error_cause_codes {
+    ($($(#[$meta:meta])* $variant:ident = $code:literal => $description:literal,)+) => {
+        /// Error codes from the MercadoPago catalogue, found on [`ErrorCause::code`].
+        ///
+        /// Codes missing from this list are kept as [`ErrorCauseCode::Unknown`].
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum ErrorCauseCode {
+            $($(#[$meta])* $variant,)+
+            Unknown(String),
+        }
+
+        impl ErrorCauseCode {
+            pub fn from_code(code: &str) -> Self {
+                match code {
+                    $($code => Self::$variant,)+
+                    other => Self::Unknown(other.to_string()),
+                }
+            }
+
+            /// Code as sent by MercadoPago, such as `"2067"`.
+            pub fn code(&self) -> &str {
+                match self {
+                    $(Self::$variant => $code,)+
+                    Self::Unknown(code) => code,
+                }
+            }
+
+            /// Human-readable description of the code, as documented by MercadoPago.
+            pub fn description(&self) -> &str {
+                match self {
+                    $(Self::$variant => $description,)+
+                    Self::Unknown(_) => "Unknown error.",
+                }
+            }
+        }
+    };
+}
+
+error_cause_codes! {
+    ParamsError = "1" => "Params error.",
+    TestTokenRequired = "3" => "Token must be for test.",
+    MissingAccessToken = "5" => "Must provide your access_token to proceed.",
+    InvalidDateFormat = "23" => "The following parameters must be valid date and format (yyyy-MM-dd'T'HH:mm:ssz).",
+    RowsLimitExceeded = "1000" => "Number of rows exceeded the limits.",
+    InvalidDateTimeFormat = "1001" => "Date format must be yyyy-MM-dd'T'HH:mm:ss.SSSZ.",
+    DuplicatedRequest = "2001" => "Already posted the same request in the last minute.",
+    CustomerNotFound = "2002" => "Customer not found.",
+    GatewayTransactionsFailure = "2004" => "POST to Gateway Transactions API fail.",
+    CardTokenNotFound = "2006" => "Card token not found.",
+    CardTokenApiFailure = "2007" => "Connection to Card Token API fail.",
+    MissingCardTokenIssuer = "2009" => "Card token issuer can't be null.",
+    CustomerEqualsCollector = "2060" => "The customer can't be equal to the collector.",
+    InvalidCardToken = "2062" => "Invalid card token.",
+    InvalidIdentificationNumber = "2067" => "Invalid user identification number.",
+    InvalidTransactionAmountValue = "2072" => "Invalid value for transaction_amount.",
+    MissingCardholderName = "3000" => "You must provide your cardholder_name with your card data.",
+    MissingCardIssuerId = "3001" => "You must provide your cardissuer_id with your card data.",
+    InvalidCardTokenId = "3003" => "Invalid card_token_id.",
+    InvalidSiteId = "3004" => "Invalid parameter site_id.",
+    /// The resource is in a state that does not allow this operation.
+    InvalidAction = "3005" => "Not valid action, the resource is in a state that does not allow this operation.",
+    InvalidCardTokenIdParameter = "3006" => "Invalid parameter cardtoken_id.",
+    MissingClientId = "3007" => "The parameter client_id can not be null.",
+    CardTokenMissing = "3008" => "Not found Cardtoken.",
+    UnauthorizedClientId = "3009" => "Unauthorized client_id.",
+    CardNotWhitelisted = "3010" => "Not found card on whitelist.",
+    PaymentMethodNotFound = "3011" => "Not found payment_method.",
+    InvalidSecurityCodeLength = "3012" => "Invalid parameter security_code_length.",
+    MissingSecurityCode = "3013" => "The parameter security_code is a required field can not be null or empty.",
+    InvalidPaymentMethod = "3014" => "Invalid parameter payment_method.",
+    InvalidCardNumberLength = "3015" => "Invalid parameter card_number_length.",
+    InvalidCardNumber = "3016" => "Invalid parameter card_number.",
+    MissingCardNumberId = "3017" => "The parameter card_number_id can not be null or empty.",
+    MissingExpirationMonth = "3018" => "The parameter expiration_month can not be null or empty.",
+    MissingExpirationYear = "3019" => "The parameter expiration_year can not be null or empty.",
+    MissingCardholderNameParameter = "3020" => "The parameter cardholder.name can not be null or empty.",
+    MissingCardholderDocumentNumber = "3021" => "The parameter cardholder.document.number can not be null or empty.",
+    MissingCardholderDocumentType = "3022" => "The parameter cardholder.document.type can not be null or empty.",
+    MissingCardholderDocumentSubtype = "3023" => "The parameter cardholder.document.subtype can not be null or empty.",
+    PartialRefundUnsupported = "3024" => "Not valid action - partial refund unsupported for this transaction.",
+    InvalidCode = "3025" => "Invalid Code.",
+    InvalidCardIdForPaymentMethod = "3026" => "Invalid card_id for this payment_method_id.",
+    InvalidPaymentTypeId = "3027" => "Invalid payment_type_id.",
+    InvalidPaymentMethodId = "3028" => "Invalid payment_method_id.",
+    InvalidExpirationMonth = "3029" => "Invalid card expiration month.",
+    InvalidExpirationYear = "3030" => "Invalid card expiration year.",
+    InvalidCardNumberValidation = "3034" => "Invalid card_number_validation.",
+    MissingCard = "4000" => "card attribute can't be null.",
+    MissingPaymentMethodId = "4001" => "payment_method_id attribute can't be null.",
+    MissingTransactionAmount = "4002" => "transaction_amount attribute can't be null.",
+    NonNumericTransactionAmount = "4003" => "transaction_amount attribute must be numeric.",
+    MissingInstallments = "4004" => "installments attribute can't be null.",
+    NonNumericInstallments = "4005" => "installments attribute must be numeric.",
+    MalformedPayer = "4006" => "payer attribute is malformed.",
+    MissingSiteId = "4007" => "site_id attribute can't be null.",
+    MissingPayerId = "4012" => "payer.id attribute can't be null.",
+    MissingPayerType = "4013" => "payer.type attribute can't be null.",
+    MissingPaymentMethodReferenceId = "4015" => "payment_method_reference_id attribute can't be null.",
+    NonNumericPaymentMethodReferenceId = "4016" => "payment_method_reference_id attribute must be numeric.",
+    MissingStatus = "4017" => "status attribute can't be null.",
+    MissingPaymentId = "4018" => "payment_id attribute can't be null.",
+    NonNumericPaymentId = "4019" => "payment_id attribute must be numeric.",
+    InvalidNotificationUrl = "4020" => "notification_url attribute must be url valid.",
+    NotificationUrlTooLong = "4021" => "notification_url attribute must be shorter than 500 character.",
+    InvalidMetadata = "4022" => "metadata attribute must be a valid JSON.",
+    MissingRefundAmount = "4023" => "transaction_amount attribute can't be null.",
+    NonNumericRefundAmount = "4024" => "transaction_amount attribute must be numeric.",
+    MissingRefundId = "4025" => "refund_id can't be null.",
+    InvalidCouponAmount = "4026" => "Invalid coupon_amount.",
+    NonNumericCampaignId = "4027" => "campaign_id attribute must be numeric.",
+    NonNumericCouponAmount = "4028" => "coupon_amount attribute must be numeric.",
+    InvalidPayerType = "4029" => "Invalid payer type.",
+    InvalidTransactionAmount = "4037" => "Invalid transaction_amount.",
+    ApplicationFeeTooBig = "4038" => "application_fee cannot be bigger than transaction_amount.",
+    NegativeApplicationFee = "4039" => "application_fee cannot be a negative value.",
+    InvalidPayerEmail = "4050" => "payer.email must be a valid email.",
+    PayerEmailTooLong = "4051" => "payer.email must be shorter than 254 characters.",
+    InvalidExpirationDate = "7523" => "Invalid expiration date.",
+}
+
+impl ErrorCauseCode {
+    /// Whether the same request may succeed if sent again, since the failure happened inside
+    /// MercadoPago.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::GatewayTransactionsFailure | Self::CardTokenApiFailure
+        )
+    }
+
+    /// Whether the request was rejected because of data typed by the buyer, such as card or
+    /// personal information, which can be shown back to them to be fixed.
+    pub fn is_user_input_error(&self) -> bool {
+        matches!(
+            self,
+            Self::InvalidIdentificationNumber
+                | Self::MissingCardholderName
+                | Self::InvalidSecurityCodeLength
+                | Self::MissingSecurityCode
+                | Self::InvalidCardNumberLength
+                | Self::InvalidCardNumber
+                | Self::MissingExpirationMonth
+                | Self::MissingExpirationYear
+                | Self::MissingCardholderNameParameter
+                | Self::MissingCardholderDocumentNumber
+                | Self::MissingCardholderDocumentType
+                | Self::InvalidExpirationMonth
+                | Self::InvalidExpirationYear
+                | Self::InvalidCardNumberValidation
+                | Self::InvalidPayerEmail
+                | Self::PayerEmailTooLong
+                | Self::InvalidExpirationDate
+        )
+    }
+}
+
+impl fmt::Display for ErrorCauseCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for ErrorCauseCode {
+    /// Accepts codes sent either as a number or as a string.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let code = deserialize_string_from_number(deserializer)?;
+        Ok(Self::from_code(&code))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_api_error_deserialization() {
+        let api_error = serde_json::from_str::<ApiError>(
+            r#"{
+                "message": "Invalid user identification number",
+                "error": "bad_request",
+                "status": 400,
+                "cause": [{"code": 2067, "description": "Invalid user identification number."}]
+            }"#,
+        )
+        .unwrap();
+
+        match api_error.into_sdk_error(400) {
+            SDKError::Api { status, causes, .. } => {
+                assert_eq!(status, 400);
+                assert_eq!(causes[0].code, ErrorCauseCode::InvalidIdentificationNumber);
+                assert!(causes[0].code.is_user_input_error());
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn t_unknown_error_cause_code() {
+        let cause = serde_json::from_str::<ErrorCause>(r#"{"code": "999999"}"#).unwrap();
+
+        assert_eq!(cause.code, ErrorCauseCode::Unknown("999999".to_string()));
+        assert_eq!(cause.code.code(), "999999");
+        assert!(!cause.code.is_retryable());
+        assert!(!cause.code.is_user_input_error());
+    }
 }
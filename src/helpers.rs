@@ -1,19 +1,99 @@
-use serde::Serializer;
+use serde::{Deserialize, Deserializer};
 
-pub trait Stringify {
-    fn stringify(&self) -> Option<String>;
-}
-
-impl Stringify for Option<i64> {
-    fn stringify(&self) -> Option<String> {
-        self.map(|c| c.to_string())
+/// Deserializes an optional string, which MercadoPago sometimes sends as a number, such as zip
+/// codes.
+pub fn deserialize_option_string_from_number<'de, D>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StringOrNumber {
+        String(String),
+        Number(serde_json::Number),
     }
+
+    Ok(
+        Option::<StringOrNumber>::deserialize(deserializer)?.map(|value| match value {
+            StringOrNumber::String(s) => s,
+            StringOrNumber::Number(n) => n.to_string(),
+        }),
+    )
 }
 
-pub fn option_stringify<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
-where
-    T: Stringify,
-    S: Serializer,
-{
-    serializer.serialize_str(&*value.stringify().unwrap())
+/// Declares an enum of the string values of a MercadoPago field, keeping values missing from the
+/// list in an `Unknown(String)` variant, so new values never break deserialization.
+///
+/// The fallback variant can be renamed with a trailing `_ => Name,` arm.
+macro_rules! string_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident = $value:literal,)+
+        }
+    ) => {
+        string_enum! {
+            $(#[$meta])*
+            pub enum $name {
+                $($(#[$variant_meta])* $variant = $value,)+
+                _ => Unknown,
+            }
+        }
+    };
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident = $value:literal,)+
+            _ => $fallback:ident,
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant,)+
+            $fallback(String),
+        }
+
+        impl $name {
+            /// Value as sent by MercadoPago.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $value,)+
+                    Self::$fallback(value) => value,
+                }
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                match value {
+                    $($value => Self::$variant,)+
+                    other => Self::$fallback(other.to_string()),
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = String::deserialize(deserializer)?;
+                Ok(Self::from(value.as_str()))
+            }
+        }
+    };
 }
+
+pub(crate) use string_enum;
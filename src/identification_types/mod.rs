@@ -0,0 +1,6 @@
+//! Document types accepted to identify payers and cardholders on the country of your account.
+//!
+//! Official API page: https://www.mercadopago.com.br/developers/en/reference/identification_types/_identification_types/get
+
+pub mod responses;
+pub mod validation;
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+use crate::payments::requests::DocumentType;
+
+/// An entry of
+/// [`MercadoPagoSDK::get_identification_types`](crate::MercadoPagoSDK::get_identification_types).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IdentificationTypeInfo {
+    pub id: DocumentType,
+    pub name: String,
+
+    /// Whether numbers of this type are made of digits only (`number`), or may have letters and
+    /// separators (`string`).
+    #[serde(rename = "type")]
+    pub type_field: String,
+
+    pub min_length: i64,
+    pub max_length: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_deserialization() {
+        let types = serde_json::from_slice::<Vec<IdentificationTypeInfo>>(include_bytes!(
+            "../../tests/assets/identification_types_response.json"
+        ))
+        .unwrap();
+
+        assert_eq!(types[0].id, DocumentType::CPF);
+        assert_eq!(types[0].max_length, 11);
+        assert_eq!(types[2].id, DocumentType::Other("Otro".to_string()));
+    }
+}
@@ -0,0 +1,218 @@
+//! Offline check-digit validation of personal identification numbers, catching typos before
+//! they reach MercadoPago.
+//!
+//! Only documents with a published check-digit algorithm are checked: CPF, CNPJ, CUIT, CUIL and
+//! RUT. Numbers of every other [`DocumentType`] are only checked to not be empty.
+
+use crate::errors::ValidationError;
+use crate::payments::requests::DocumentType;
+
+/// Checks `number` against the check digits of `document_type`. Dots, dashes, slashes and spaces
+/// are ignored.
+pub fn validate(document_type: &DocumentType, number: &str) -> Result<(), ValidationError> {
+    let is_valid = match document_type {
+        DocumentType::CPF => is_valid_cpf(number),
+        DocumentType::CNPJ => is_valid_cnpj(number),
+        DocumentType::CUIT | DocumentType::CUIL => is_valid_cuit(number),
+        DocumentType::RUT => is_valid_rut(number),
+        _ => !number.trim().is_empty(),
+    };
+
+    if is_valid {
+        Ok(())
+    } else {
+        // the number is left out, since errors usually end up in logs
+        Err(ValidationError::Identification(format!(
+            "Invalid {} number.",
+            document_type
+        )))
+    }
+}
+
+/// Brazilian individuals: 11 digits, the last two being check digits.
+pub fn is_valid_cpf(number: &str) -> bool {
+    let digits = match to_digits(number) {
+        Some(digits) if digits.len() == 11 => digits,
+        _ => return false,
+    };
+
+    // Sequences such as 111.111.111-11 pass the check digits, but are never issued.
+    if digits.iter().all(|d| *d == digits[0]) {
+        return false;
+    }
+
+    (9..11).all(|len| {
+        let sum: u32 = digits[..len]
+            .iter()
+            .zip((2..=len as u32 + 1).rev())
+            .map(|(d, w)| d * w)
+            .sum();
+
+        digits[len] == mod11_check_digit(sum)
+    })
+}
+
+/// Brazilian companies: 14 characters, the last two being check digits.
+///
+/// The first 12 may be uppercase letters, as in the alphanumeric CNPJ issued from July 2026.
+pub fn is_valid_cnpj(number: &str) -> bool {
+    let chars = strip_separators(number).to_ascii_uppercase();
+    let chars = chars.as_bytes();
+
+    if chars.len() != 14
+        || !chars[..12].iter().all(u8::is_ascii_alphanumeric)
+        || !chars[12..].iter().all(u8::is_ascii_digit)
+        || chars.iter().all(|c| *c == chars[0])
+    {
+        return false;
+    }
+
+    // Letters are worth their ASCII code minus 48, as digits are.
+    let values = chars.iter().map(|c| (c - b'0') as u32).collect::<Vec<_>>();
+
+    (12..14).all(|len| {
+        let sum: u32 = values[..len]
+            .iter()
+            .rev()
+            .zip((2..=9).cycle())
+            .map(|(v, w)| v * w)
+            .sum();
+
+        values[len] == mod11_check_digit(sum)
+    })
+}
+
+/// Argentine CUIT and CUIL: 11 digits, such as 20-12345678-6, the last being a check digit.
+pub fn is_valid_cuit(number: &str) -> bool {
+    const WEIGHTS: [u32; 10] = [5, 4, 3, 2, 7, 6, 5, 4, 3, 2];
+
+    let digits = match to_digits(number) {
+        Some(digits) if digits.len() == 11 => digits,
+        _ => return false,
+    };
+
+    let prefix = digits[0] * 10 + digits[1];
+    if !matches!(prefix, 20 | 23 | 24 | 25 | 26 | 27 | 30 | 33 | 34) {
+        return false;
+    }
+
+    let sum: u32 = digits.iter().zip(WEIGHTS).map(|(d, w)| d * w).sum();
+
+    match 11 - sum % 11 {
+        11 => digits[10] == 0,
+        // No check digit exists for such numbers, which get another prefix instead.
+        10 => false,
+        check => digits[10] == check,
+    }
+}
+
+/// Chilean RUT: digits followed by a check digit which may be a `K`, such as 12.345.678-5.
+pub fn is_valid_rut(number: &str) -> bool {
+    let mut body = strip_separators(number).to_ascii_uppercase();
+    let check = body.pop();
+
+    let digits = match to_digits(&body) {
+        Some(digits) if (1..=8).contains(&digits.len()) => digits,
+        _ => return false,
+    };
+
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .zip((2..=7).cycle())
+        .map(|(d, w)| d * w)
+        .sum();
+
+    let expected = match 11 - sum % 11 {
+        11 => '0',
+        10 => 'K',
+        check => char::from_digit(check, 10).unwrap(),
+    };
+
+    check == Some(expected)
+}
+
+fn strip_separators(number: &str) -> String {
+    number
+        .chars()
+        .filter(|c| !matches!(c, '.' | '-' | '/' | ' '))
+        .collect()
+}
+
+fn to_digits(number: &str) -> Option<Vec<u32>> {
+    strip_separators(number)
+        .chars()
+        .map(|c| c.to_digit(10))
+        .collect()
+}
+
+/// Check digit shared by CPF and CNPJ.
+fn mod11_check_digit(sum: u32) -> u32 {
+    match sum % 11 {
+        0 | 1 => 0,
+        rest => 11 - rest,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_cpf() {
+        assert!(is_valid_cpf("12345678909"));
+        assert!(is_valid_cpf("191.191.191-00"));
+        assert!(!is_valid_cpf("12345678900"));
+        assert!(!is_valid_cpf("111.111.111-11"));
+        assert!(!is_valid_cpf("1234567890"));
+    }
+
+    #[test]
+    fn t_cnpj() {
+        assert!(is_valid_cnpj("11.222.333/0001-81"));
+        assert!(!is_valid_cnpj("11.222.333/0001-82"));
+        assert!(!is_valid_cnpj("00000000000000"));
+
+        assert!(is_valid_cnpj("12.ABC.345/01DE-35"));
+        assert!(is_valid_cnpj("12abc34501de35"));
+        assert!(!is_valid_cnpj("12.ABC.345/01DE-36"));
+        assert!(!is_valid_cnpj("12.ABC.345/01DE-3A"));
+    }
+
+    #[test]
+    fn t_cuit() {
+        assert!(is_valid_cuit("20-12345678-6"));
+        assert!(is_valid_cuit("30500010912"));
+        assert!(!is_valid_cuit("20-12345678-5"));
+        assert!(!is_valid_cuit("10-12345678-6"));
+    }
+
+    #[test]
+    fn t_rut() {
+        assert!(is_valid_rut("12.345.678-5"));
+        assert!(is_valid_rut("11.111.111-1"));
+        assert!(is_valid_rut("10.000.013-k"));
+        assert!(!is_valid_rut("12.345.678-K"));
+        assert!(!is_valid_rut("5"));
+        assert!(!is_valid_rut("1é"));
+        assert!(!is_valid_rut("1234567é"));
+    }
+
+    #[test]
+    fn t_validate() {
+        assert!(validate(&DocumentType::CUIL, "20-12345678-6").is_ok());
+        assert!(validate(&DocumentType::CURP, "GODE561231HDFRRN09").is_ok());
+        assert!(matches!(
+            validate(&DocumentType::CPF, "12345678900"),
+            Err(ValidationError::Identification(_))
+        ));
+        assert!(validate(&DocumentType::Other("Otro".to_string()), " ").is_err());
+    }
+
+    #[test]
+    fn t_error_omits_number() {
+        let err = validate(&DocumentType::CPF, "12345678900").unwrap_err();
+
+        assert!(!err.to_string().contains("12345678900"));
+    }
+}
@@ -15,6 +15,14 @@
 //! mercadopago_sdk_rust = "0.1"
 //! ```
 //!
+//! ## Features
+//!
+//! * `tracing`: emits a `mercadopago.request` span for every request, with method, path, status,
+//!   latency and MercadoPago's `x-request-id`. Bodies are only logged at trace level, with personal
+//!   and card data redacted.
+//! * `axum` and `actix-web`: webhook receivers for each framework, found on [`webhooks`], verifying
+//!   notifications before dispatching them to your callback.
+//!
 //! # Usage
 //!
 //! The client is built using the
@@ -32,7 +40,18 @@
 //!
 //! Once the token is inserted, you can call methods on [`crate::MercadoPagoSDK`]
 //!
+//! When you need to change where requests are sent, such as a local stub server or a corporate
+//! proxy, use [`MercadoPagoSDKBuilder::new`] along with its setters instead.
 //!
+//! ```rust
+//! # fn main() {
+//! use mercadopago_sdk_rust::{MercadoPagoSDK, MercadoPagoSDKBuilder};
+//!
+//! let mp_sdk: MercadoPagoSDK = MercadoPagoSDKBuilder::new("MP_ACCESS_TOKEN")
+//!     .set_base_url("http://localhost:8080")
+//!     .build();
+//! # }
+//! ```
 //!
 //! # Creating a CheckoutPro Preference
 //! ```no_run
@@ -53,7 +72,7 @@
 //!         .set_payer(CheckoutProPayer::minimal_payer(
 //!             "fulano@beltrano.com.br".to_string(),
 //!             DocumentType::CPF,
-//!             41810524485,
+//!             "41810524431",
 //!         ));
 //!
 //!     mp_sdk
@@ -74,186 +93,768 @@
 
 pub mod card_tokens;
 pub mod common_types;
+pub mod customers;
 pub mod errors;
 pub mod helpers;
+pub mod identification_types;
+pub mod merchant_orders;
+mod oauth;
+pub mod payment_methods;
 pub mod payments;
 pub mod preferences;
+pub mod refunds;
+pub mod retry;
+pub mod sensitive;
+#[cfg(feature = "tracing")]
+mod telemetry;
+pub mod transport;
 pub mod webhooks;
 
 use std::marker::PhantomData;
+use std::sync::Arc;
 
-use futures::future::err;
-use futures::TryFutureExt;
-use oauth2::basic::BasicClient;
-use oauth2::reqwest::async_http_client;
-use oauth2::{
-    AccessToken, AuthType, AuthUrl, ClientId, ClientSecret, Scope, TokenResponse, TokenUrl,
-};
+use reqwest::header::HeaderValue;
 use reqwest::{Client, Method, RequestBuilder};
 use serde::de::DeserializeOwned;
+use uuid::Uuid;
 
 use crate::card_tokens::requests::CardTokenOptions;
 use crate::card_tokens::responses::CardTokenResponse;
-use crate::errors::{ApiError, SDKError};
-use crate::payments::requests::CreatePaymentPayload;
+use crate::customers::requests::{
+    CreateCustomerCardPayload, CreateCustomerPayload, CustomerSearchOptions, UpdateCustomerPayload,
+};
+use crate::customers::responses::{CustomerCard, CustomerResponse, CustomerSearchResponse};
+use crate::errors::{ApiError, SDKError, ValidationError, WebhookError};
+use crate::identification_types::responses::IdentificationTypeInfo;
+use crate::merchant_orders::responses::MerchantOrderResponse;
+use crate::oauth::{ClientCredentials, ClientCredentialsPayload, OAuthTokenResponse};
+use crate::payment_methods::requests::InstallmentsQuery;
+use crate::payment_methods::responses::{InstallmentOptions, PaymentMethodInfo};
+use crate::payments::requests::{
+    CreatePaymentPayload, PaymentMethodId, PaymentSearchOptions, UpdatePaymentPayload,
+};
+use crate::payments::responses::{CreatePaymentResponse, PaymentSearchResponse, PaymentStatus};
 use crate::preferences::requests::CheckoutProPreferences;
 use crate::preferences::responses::CheckoutProPreferencesResponse;
+use crate::refunds::requests::CreateRefundPayload;
+use crate::refunds::responses::RefundResponse;
+use crate::retry::RetryPolicy;
+use crate::sensitive::SensitiveString;
+use crate::transport::HttpTransport;
+use crate::webhooks::{IpnNotification, WebhookRequest, WebhookResource, WebhookTopic};
 
-const API_BASE_URL: &str = "https://api.mercadopago.com";
+/// Default URL of the MercadoPago API.
+pub const API_BASE_URL: &str = "https://api.mercadopago.com";
 
+/// Header used by MercadoPago to tell retries of a request apart from new requests.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "X-Idempotency-Key";
+
+/// Builds a [`MercadoPagoSDK`].
+///
+/// Use [`MercadoPagoSDKBuilder::with_token`] for the defaults, or [`MercadoPagoSDKBuilder::new`]
+/// to customize the base URL and the HTTP transport before calling
+/// [`MercadoPagoSDKBuilder::build`].
 ///
+/// Applications without an access token start from [`MercadoPagoSDKBuilder::with_credentials`],
+/// and finish with [`MercadoPagoSDKBuilder::authorize`] instead.
 #[derive(Debug)]
-pub struct MercadoPagoSDKBuilder {}
+pub struct MercadoPagoSDKBuilder {
+    access_token: SensitiveString,
+    credentials: Option<ClientCredentials>,
+    base_url: String,
+    transport: Option<Arc<dyn HttpTransport>>,
+    automatic_idempotency_keys: bool,
+    retry_policy: RetryPolicy,
+}
 
 impl MercadoPagoSDKBuilder {
-    async fn authorize<T: ToString>(
-        client_id: T,
-        client_secret: T,
-    ) -> Result<MercadoPagoSDK, SDKError> {
-        let client = BasicClient::new(
-            ClientId::new(client_id.to_string()),
-            Some(ClientSecret::new(client_secret.to_string())),
-            AuthUrl::new("https://auth.mercadopago.com/authorization".to_string()).unwrap(),
-            Some(TokenUrl::new("https://api.mercadopago.com/oauth/token".to_string()).unwrap()),
-        )
-        .set_auth_type(AuthType::BasicAuth);
-
-        let token_response = client
-            .exchange_client_credentials()
-            .add_scope(Scope::new("offline_access".to_string()))
-            .request_async(async_http_client)
-            .await
-            .unwrap();
+    /// Creates a builder which gets its access token from the application credentials, on
+    /// [`MercadoPagoSDKBuilder::authorize`].
+    pub fn with_credentials<T: ToString>(client_id: T, client_secret: T) -> Self {
+        Self {
+            credentials: Some(ClientCredentials {
+                client_id: client_id.to_string(),
+                client_secret: SensitiveString::new(client_secret.to_string()),
+            }),
+            ..Self::new("")
+        }
+    }
 
-        Ok(MercadoPagoSDK {
-            http_client: Default::default(),
-            access_token: token_response.access_token().clone(),
-        })
+    /// Exchanges the credentials given to [`MercadoPagoSDKBuilder::with_credentials`] for an
+    /// access token, and creates an [`MercadoPagoSDK`] with it.
+    ///
+    /// The token is requested from `/oauth/token` of the configured base URL, through the
+    /// configured transport and retry policy.
+    pub async fn authorize(mut self) -> Result<MercadoPagoSDK, SDKError> {
+        let credentials = self.credentials.take().ok_or_else(|| {
+            SDKError::CredentialsError(
+                "Only builders created with credentials can be authorized.".to_string(),
+            )
+        })?;
+
+        let mut sdk = self.build();
+        let request = sdk
+            .http_client
+            .request(Method::POST, sdk.url("/oauth/token"))
+            .json(&ClientCredentialsPayload::from(&credentials));
+
+        let token = SDKRequest::<OAuthTokenResponse> {
+            access_token: None,
+            ..sdk.sdk_request(request)
+        }
+        .execute()
+        .await
+        .map_err(|e| SDKError::CredentialsError(e.to_string()))?;
+
+        sdk.access_token = token.access_token;
+        Ok(sdk)
     }
 
     /// Creates an [`MercadoPagoSDK`] ready to request the API.
     pub fn with_token<T: ToString>(client_access_token: T) -> MercadoPagoSDK {
+        Self::new(client_access_token).build()
+    }
+
+    /// Creates a builder with the default base URL and transport.
+    pub fn new<T: ToString>(client_access_token: T) -> Self {
+        Self {
+            access_token: SensitiveString::new(client_access_token.to_string()),
+            credentials: None,
+            base_url: API_BASE_URL.to_string(),
+            transport: None,
+            automatic_idempotency_keys: false,
+            retry_policy: RetryPolicy::never(),
+        }
+    }
+
+    /// Sets the URL every endpoint is appended to. Defaults to [`API_BASE_URL`].
+    pub fn set_base_url<T: ToString>(mut self, base_url: T) -> Self {
+        self.base_url = base_url.to_string().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Sets the [`HttpTransport`] used to send requests. Defaults to a [`reqwest::Client`].
+    pub fn set_transport<HT: HttpTransport + 'static>(mut self, transport: HT) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// When enabled, every `POST`, `PUT` and `PATCH` request without an explicit
+    /// [`SDKRequest::idempotency_key`] gets a random one, generated once per [`SDKRequest`] and
+    /// reused by all of its attempts. Disabled by default.
+    pub fn set_automatic_idempotency_keys(mut self, enabled: bool) -> Self {
+        self.automatic_idempotency_keys = enabled;
+        self
+    }
+
+    /// Sets how transient failures are retried. Defaults to [`RetryPolicy::never`].
+    pub fn set_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Creates the [`MercadoPagoSDK`]. Builders created with
+    /// [`MercadoPagoSDKBuilder::with_credentials`] have no access token until
+    /// [`MercadoPagoSDKBuilder::authorize`]d.
+    pub fn build(self) -> MercadoPagoSDK {
+        let http_client = Client::new();
+        let transport = self
+            .transport
+            .unwrap_or_else(|| Arc::new(http_client.clone()));
+
         MercadoPagoSDK {
-            http_client: Default::default(),
-            access_token: AccessToken::new(client_access_token.to_string()),
+            http_client,
+            transport,
+            base_url: self.base_url,
+            access_token: self.access_token,
+            automatic_idempotency_keys: self.automatic_idempotency_keys,
+            retry_policy: self.retry_policy,
         }
     }
 }
 
 #[derive(Debug)]
 pub struct MercadoPagoSDK {
+    /// Only used to build requests, which are sent through `transport`.
     pub(crate) http_client: Client,
-    pub(crate) access_token: AccessToken,
+    pub(crate) transport: Arc<dyn HttpTransport>,
+    pub(crate) base_url: String,
+    pub(crate) access_token: SensitiveString,
+    pub(crate) automatic_idempotency_keys: bool,
+    pub(crate) retry_policy: RetryPolicy,
 }
 
 #[derive(Debug)]
 pub struct SDKRequest<'a, RP> {
-    http_client: &'a Client,
-    access_token: &'a AccessToken,
+    transport: &'a dyn HttpTransport,
+    /// Absent when requesting the access token itself.
+    access_token: Option<&'a SensitiveString>,
     request: RequestBuilder,
+    idempotency_key: Option<String>,
+    automatic_idempotency_key: bool,
+    retry_policy: RetryPolicy,
     response_type: PhantomData<RP>,
 }
 
 impl<'a, RP> SDKRequest<'a, RP> {
-    /// Injects bearer token, and return response
+    /// Sends `key` as the `X-Idempotency-Key` header, so MercadoPago processes this operation only
+    /// once, no matter how many times it is sent.
+    ///
+    /// Reuse the same key when retrying the same logical operation, such as after a timeout.
+    pub fn idempotency_key<T: ToString>(mut self, key: T) -> Self {
+        self.idempotency_key = Some(key.to_string());
+        self
+    }
+
+    /// Injects bearer token, and return response.
+    ///
+    /// Transient failures are retried according to the [`RetryPolicy`] of the SDK.
     pub async fn execute(self) -> Result<RP, SDKError>
     where
         RP: DeserializeOwned,
     {
-        let request = self
-            .request
-            .bearer_auth(self.access_token.secret())
-            .build()
-            .unwrap();
-        let response = self
-            .http_client
-            .execute(request)
-            .and_then(|c| c.text())
-            .await?;
-        eprintln!("response = {}", response);
+        let mut request = match self.access_token {
+            Some(access_token) => self.request.bearer_auth(access_token.expose()),
+            None => self.request,
+        }
+        .build()?;
 
-        // matches errors due to wrong payloads etc
-        let error_jd = serde_json::from_str::<ApiError>(&*response);
-        if let Ok(err) = error_jd {
-            eprintln!("err = {:#?}", err);
-            return Err(SDKError::GenericError);
+        let idempotency_key = self.idempotency_key.or_else(|| {
+            let is_mutating = matches!(
+                *request.method(),
+                Method::POST | Method::PUT | Method::PATCH
+            );
+            (self.automatic_idempotency_key && is_mutating).then(|| Uuid::new_v4().to_string())
+        });
+        let can_retry = self
+            .retry_policy
+            .allows_retries(request.method(), idempotency_key.is_some());
+        if let Some(key) = idempotency_key {
+            request.headers_mut().insert(
+                IDEMPOTENCY_KEY_HEADER,
+                HeaderValue::from_str(&key).map_err(|_| {
+                    ValidationError::IdempotencyKeyError(
+                        "Idempotency keys must be visible ASCII characters.".to_string(),
+                    )
+                })?,
+            );
         }
 
-        let jd = &mut serde_json::Deserializer::from_str(&*response);
-        let res: Result<RP, _> = serde_path_to_error::deserialize(jd);
+        #[cfg(feature = "tracing")]
+        let (span, started_at) = (telemetry::request_span(&request), std::time::Instant::now());
+
+        let send_with_retries = async {
+            let mut attempt = 1;
+            loop {
+                let next_request = if can_retry && attempt < self.retry_policy.max_attempts {
+                    request.try_clone()
+                } else {
+                    None
+                };
+
+                let result = self.transport.send(request).await;
+                #[cfg(feature = "tracing")]
+                telemetry::record_attempt(&span, attempt, &result);
 
-        match res {
-            Ok(deserialized_resp) => Ok(deserialized_resp),
-            Err(wow) => {
-                println!("{:?}", wow.path());
-                eprintln!("Error = {:#?}", wow);
-                Err(SDKError::GenericError)
+                match next_request {
+                    Some(next_request) if self.retry_policy.should_retry(&result) => {
+                        let delay = self.retry_policy.delay(attempt, result.as_ref().ok());
+                        tokio::time::sleep(delay).await;
+
+                        request = next_request;
+                        attempt += 1;
+                    }
+                    _ => break result,
+                }
             }
+        };
+        #[cfg(feature = "tracing")]
+        let send_with_retries = tracing::Instrument::instrument(send_with_retries, span.clone());
+        let response = send_with_retries.await?;
+
+        #[cfg(feature = "tracing")]
+        telemetry::record_response(&span, &response, started_at.elapsed());
+
+        // matches errors due to wrong payloads etc
+        if !response.status.is_success() {
+            let status = response.status.as_u16();
+            return Err(match serde_json::from_str::<ApiError>(&response.body) {
+                Ok(api_error) => api_error.into_sdk_error(status),
+                Err(_) => SDKError::Api {
+                    status,
+                    message: response.body,
+                    error: String::new(),
+                    causes: vec![],
+                },
+            });
         }
+
+        let jd = &mut serde_json::Deserializer::from_str(&response.body);
+        serde_path_to_error::deserialize(jd).map_err(|e| SDKError::DeserializationError {
+            path: e.path().to_string(),
+            source: e.into_inner(),
+            body: response.body.clone(),
+        })
     }
 }
 
 impl MercadoPagoSDK {
+    /// Joins `path` to the configured base URL.
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    fn sdk_request<RP>(&self, request: RequestBuilder) -> SDKRequest<'_, RP> {
+        SDKRequest {
+            transport: &*self.transport,
+            access_token: Some(&self.access_token),
+            request,
+            idempotency_key: None,
+            automatic_idempotency_key: self.automatic_idempotency_keys,
+            retry_policy: self.retry_policy,
+            response_type: PhantomData::<_>,
+        }
+    }
+
     pub fn create_preferences_checkout_pro(
         &self,
         opts: CheckoutProPreferences,
-    ) -> Result<SDKRequest<CheckoutProPreferencesResponse>, SDKError> {
-        if opts.validate() {}
+    ) -> Result<SDKRequest<'_, CheckoutProPreferencesResponse>, SDKError> {
+        if !opts.validate() {
+            return Err(ValidationError::PayerError(
+                "The payer personal identification is not valid.".to_string(),
+            )
+            .into());
+        }
 
         let request = self
             .http_client
-            .request(
-                Method::POST,
-                API_BASE_URL.to_string() + "/checkout/preferences",
-            )
+            .request(Method::POST, self.url("/checkout/preferences"))
             .json(&opts);
 
-        Ok(SDKRequest {
-            http_client: &self.http_client,
-            access_token: &self.access_token,
-            request,
-            response_type: PhantomData::<_>,
-        })
+        Ok(self.sdk_request(request))
     }
 
     /// Used to create and save a credit/debit card token, instead of transacting raw sensitive
     /// data, such as card number.
     ///
-    /// Create a token before issuing payments with cards.
+    /// Create a token before issuing payments with cards. Cards saved to a customer are tokenized
+    /// through [`CardTokenOptions::saved_card`].
+    ///
+    /// Card data is validated offline first, failing with [`ValidationError::CreditCard`] before
+    /// anything is sent. See [`CardTokenOptions::validate`].
     pub fn create_card_token(
         &self,
         opts: CardTokenOptions,
-    ) -> Result<SDKRequest<CardTokenResponse>, SDKError> {
+    ) -> Result<SDKRequest<'_, CardTokenResponse>, SDKError> {
+        opts.validate().map_err(ValidationError::from)?;
+
         let url = format!(
-            "{}/v1/card_tokens?public_key={}",
-            API_BASE_URL,
+            "{}?public_key={}",
+            self.url("/v1/card_tokens"),
             opts.public_key.as_deref().unwrap_or("")
         );
 
         let request = self.http_client.request(Method::POST, url).json(&opts);
 
-        Ok(SDKRequest {
-            http_client: &self.http_client,
-            access_token: &self.access_token,
-            request,
-            response_type: PhantomData::<_>,
-        })
+        Ok(self.sdk_request(request))
     }
 
+    /// Creates a payment. Card payments need a token from [`MercadoPagoSDK::create_card_token`].
+    ///
+    /// Every call is sent with a new `X-Idempotency-Key`, which MercadoPago requires for payment
+    /// creation. Use [`SDKRequest::idempotency_key`] to set your own.
     pub fn create_payment(
         &self,
         opts: CreatePaymentPayload,
-    ) -> Result<SDKRequest<CheckoutProPreferencesResponse>, SDKError> {
+    ) -> Result<SDKRequest<'_, CreatePaymentResponse>, SDKError> {
         let request = self
             .http_client
-            .request(Method::POST, "/payments")
+            .request(Method::POST, self.url("/v1/payments"))
             .json(&opts);
 
-        Ok(SDKRequest {
-            http_client: &self.http_client,
-            access_token: &self.access_token,
-            request,
-            response_type: PhantomData::<CheckoutProPreferencesResponse>,
-        })
+        Ok(self.sdk_request(request).idempotency_key(Uuid::new_v4()))
+    }
+
+    /// Fetches a payment by its ID.
+    pub fn get_payment(
+        &self,
+        payment_id: i64,
+    ) -> Result<SDKRequest<'_, CreatePaymentResponse>, SDKError> {
+        let request = self.http_client.request(
+            Method::GET,
+            self.url(&format!("/v1/payments/{}", payment_id)),
+        );
+
+        Ok(self.sdk_request(request))
+    }
+
+    /// Searches payments matching `filters`, one page at a time.
+    pub fn search_payments(
+        &self,
+        filters: PaymentSearchOptions,
+    ) -> Result<SDKRequest<'_, PaymentSearchResponse>, SDKError> {
+        let request = self
+            .http_client
+            .request(Method::GET, self.url("/v1/payments/search"))
+            .query(&filters);
+
+        Ok(self.sdk_request(request))
+    }
+
+    /// Changes the fields set on `patch` of an existing payment.
+    pub fn update_payment(
+        &self,
+        payment_id: i64,
+        patch: UpdatePaymentPayload,
+    ) -> Result<SDKRequest<'_, CreatePaymentResponse>, SDKError> {
+        let request = self
+            .http_client
+            .request(
+                Method::PUT,
+                self.url(&format!("/v1/payments/{}", payment_id)),
+            )
+            .json(&patch);
+
+        Ok(self.sdk_request(request))
+    }
+
+    /// Captures a payment created with `capture` set to false, charging `amount` of the reserved
+    /// value, or all of it when `amount` is `None`.
+    pub fn capture_payment(
+        &self,
+        payment_id: i64,
+        amount: Option<f64>,
+    ) -> Result<SDKRequest<'_, CreatePaymentResponse>, SDKError> {
+        let patch = UpdatePaymentPayload {
+            capture: Some(true),
+            transaction_amount: amount,
+            ..Default::default()
+        };
+
+        Ok(self
+            .update_payment(payment_id, patch)?
+            .idempotency_key(Uuid::new_v4()))
+    }
+
+    /// Cancels a payment which was not approved yet, such as authorized card payments, or
+    /// pending tickets, releasing any reserved value.
+    pub fn cancel_payment(
+        &self,
+        payment_id: i64,
+    ) -> Result<SDKRequest<'_, CreatePaymentResponse>, SDKError> {
+        let patch = UpdatePaymentPayload {
+            status: Some(PaymentStatus::Cancelled),
+            ..Default::default()
+        };
+
+        self.update_payment(payment_id, patch)
+    }
+
+    /// Refunds `amount` of a payment, or all of it when `amount` is `None`.
+    ///
+    /// Like [`MercadoPagoSDK::create_payment`], every call is sent with a new
+    /// `X-Idempotency-Key`.
+    pub fn create_refund(
+        &self,
+        payment_id: i64,
+        amount: Option<f64>,
+    ) -> Result<SDKRequest<'_, RefundResponse>, SDKError> {
+        let request = self
+            .http_client
+            .request(
+                Method::POST,
+                self.url(&format!("/v1/payments/{}/refunds", payment_id)),
+            )
+            .json(&CreateRefundPayload { amount });
+
+        Ok(self.sdk_request(request).idempotency_key(Uuid::new_v4()))
+    }
+
+    /// Lists every refund of a payment.
+    pub fn list_refunds(
+        &self,
+        payment_id: i64,
+    ) -> Result<SDKRequest<'_, Vec<RefundResponse>>, SDKError> {
+        let request = self.http_client.request(
+            Method::GET,
+            self.url(&format!("/v1/payments/{}/refunds", payment_id)),
+        );
+
+        Ok(self.sdk_request(request))
+    }
+
+    pub fn get_refund(
+        &self,
+        payment_id: i64,
+        refund_id: i64,
+    ) -> Result<SDKRequest<'_, RefundResponse>, SDKError> {
+        let request = self.http_client.request(
+            Method::GET,
+            self.url(&format!(
+                "/v1/payments/{}/refunds/{}",
+                payment_id, refund_id
+            )),
+        );
+
+        Ok(self.sdk_request(request))
+    }
+
+    /// Lists the payment methods available to your account, and the rules to validate cards of
+    /// each brand.
+    pub fn get_payment_methods(&self) -> Result<SDKRequest<'_, Vec<PaymentMethodInfo>>, SDKError> {
+        let request = self
+            .http_client
+            .request(Method::GET, self.url("/v1/payment_methods"));
+
+        Ok(self.sdk_request(request))
+    }
+
+    /// Lists the installment plans available to pay `amount` with a card, found by its `bin`
+    /// (first 6 or 8 digits), or by its payment method and issuer.
+    ///
+    /// The `installments` of a [`CreatePaymentPayload`] must be one of the returned plans.
+    pub fn get_installments(
+        &self,
+        amount: f64,
+        bin: Option<&str>,
+        payment_method_id: Option<PaymentMethodId>,
+        issuer_id: Option<i64>,
+    ) -> Result<SDKRequest<'_, Vec<InstallmentOptions>>, SDKError> {
+        let request = self
+            .http_client
+            .request(Method::GET, self.url("/v1/payment_methods/installments"))
+            .query(&InstallmentsQuery {
+                amount,
+                bin: bin.map(ToString::to_string),
+                payment_method_id,
+                issuer_id,
+            });
+
+        Ok(self.sdk_request(request))
+    }
+
+    /// Lists the document types accepted on the country of your account, along with the length of
+    /// their numbers.
+    pub fn get_identification_types(
+        &self,
+    ) -> Result<SDKRequest<'_, Vec<IdentificationTypeInfo>>, SDKError> {
+        let request = self
+            .http_client
+            .request(Method::GET, self.url("/v1/identification_types"));
+
+        Ok(self.sdk_request(request))
+    }
+
+    /// Saves a buyer as a customer, whose cards can then be saved for future payments.
+    pub fn create_customer(
+        &self,
+        opts: CreateCustomerPayload,
+    ) -> Result<SDKRequest<'_, CustomerResponse>, SDKError> {
+        let request = self
+            .http_client
+            .request(Method::POST, self.url("/v1/customers"))
+            .json(&opts);
+
+        Ok(self.sdk_request(request))
+    }
+
+    pub fn get_customer(
+        &self,
+        customer_id: &str,
+    ) -> Result<SDKRequest<'_, CustomerResponse>, SDKError> {
+        let request = self.http_client.request(
+            Method::GET,
+            self.url(&format!("/v1/customers/{}", customer_id)),
+        );
+
+        Ok(self.sdk_request(request))
+    }
+
+    /// Searches customers matching `filters`, such as by email, one page at a time.
+    pub fn search_customers(
+        &self,
+        filters: CustomerSearchOptions,
+    ) -> Result<SDKRequest<'_, CustomerSearchResponse>, SDKError> {
+        let request = self
+            .http_client
+            .request(Method::GET, self.url("/v1/customers/search"))
+            .query(&filters);
+
+        Ok(self.sdk_request(request))
+    }
+
+    /// Changes the fields set on `patch` of an existing customer.
+    pub fn update_customer(
+        &self,
+        customer_id: &str,
+        patch: UpdateCustomerPayload,
+    ) -> Result<SDKRequest<'_, CustomerResponse>, SDKError> {
+        let request = self
+            .http_client
+            .request(
+                Method::PUT,
+                self.url(&format!("/v1/customers/{}", customer_id)),
+            )
+            .json(&patch);
+
+        Ok(self.sdk_request(request))
+    }
+
+    /// Deletes a customer, along with its saved cards, returning it as it was before deletion.
+    pub fn delete_customer(
+        &self,
+        customer_id: &str,
+    ) -> Result<SDKRequest<'_, CustomerResponse>, SDKError> {
+        let request = self.http_client.request(
+            Method::DELETE,
+            self.url(&format!("/v1/customers/{}", customer_id)),
+        );
+
+        Ok(self.sdk_request(request))
+    }
+
+    /// Saves a card to a customer, from the `card_token_id` of a
+    /// [`CardTokenResponse`](crate::card_tokens::responses::CardTokenResponse).
+    pub fn save_customer_card(
+        &self,
+        customer_id: &str,
+        card_token_id: &str,
+    ) -> Result<SDKRequest<'_, CustomerCard>, SDKError> {
+        let request = self
+            .http_client
+            .request(
+                Method::POST,
+                self.url(&format!("/v1/customers/{}/cards", customer_id)),
+            )
+            .json(&CreateCustomerCardPayload {
+                token: card_token_id.to_string(),
+            });
+
+        Ok(self.sdk_request(request))
+    }
+
+    /// Lists every card saved to a customer.
+    pub fn list_customer_cards(
+        &self,
+        customer_id: &str,
+    ) -> Result<SDKRequest<'_, Vec<CustomerCard>>, SDKError> {
+        let request = self.http_client.request(
+            Method::GET,
+            self.url(&format!("/v1/customers/{}/cards", customer_id)),
+        );
+
+        Ok(self.sdk_request(request))
+    }
+
+    pub fn get_customer_card(
+        &self,
+        customer_id: &str,
+        card_id: &str,
+    ) -> Result<SDKRequest<'_, CustomerCard>, SDKError> {
+        let request = self.http_client.request(
+            Method::GET,
+            self.url(&format!("/v1/customers/{}/cards/{}", customer_id, card_id)),
+        );
+
+        Ok(self.sdk_request(request))
+    }
+
+    /// Removes a card from a customer, returning it as it was before deletion.
+    pub fn delete_customer_card(
+        &self,
+        customer_id: &str,
+        card_id: &str,
+    ) -> Result<SDKRequest<'_, CustomerCard>, SDKError> {
+        let request = self.http_client.request(
+            Method::DELETE,
+            self.url(&format!("/v1/customers/{}/cards/{}", customer_id, card_id)),
+        );
+
+        Ok(self.sdk_request(request))
+    }
+
+    pub fn get_merchant_order(
+        &self,
+        merchant_order_id: i64,
+    ) -> Result<SDKRequest<'_, MerchantOrderResponse>, SDKError> {
+        let request = self.http_client.request(
+            Method::GET,
+            self.url(&format!("/merchant_orders/{}", merchant_order_id)),
+        );
+
+        Ok(self.sdk_request(request))
+    }
+
+    /// Fetches the resource a webhook notification is about.
+    ///
+    /// Verify the notification with [`webhooks::WebhookVerifier`] before resolving it.
+    pub async fn resolve_webhook(
+        &self,
+        webhook: &WebhookRequest,
+    ) -> Result<WebhookResource, SDKError> {
+        self.resolve_resource(&webhook.type_field, &webhook.data.id)
+            .await
+    }
+
+    /// Fetches the resource an IPN notification is about.
+    pub async fn resolve_ipn(
+        &self,
+        notification: &IpnNotification,
+    ) -> Result<WebhookResource, SDKError> {
+        self.resolve_resource(&notification.topic, &notification.id)
+            .await
+    }
+
+    async fn resolve_resource(
+        &self,
+        topic: &WebhookTopic,
+        id: &str,
+    ) -> Result<WebhookResource, SDKError> {
+        let numeric_id = || {
+            id.parse::<i64>()
+                .map_err(|_| WebhookError::InvalidResourceId(id.to_string()))
+        };
+
+        let resource = match topic {
+            WebhookTopic::Payment => WebhookResource::Payment(Box::new(
+                self.get_payment(numeric_id()?)?.execute().await?,
+            )),
+            WebhookTopic::MerchantOrder | WebhookTopic::TopicMerchantOrderWh => {
+                WebhookResource::MerchantOrder(Box::new(
+                    self.get_merchant_order(numeric_id()?)?.execute().await?,
+                ))
+            }
+            WebhookTopic::SubscriptionPreapproval => WebhookResource::SubscriptionPreapproval(
+                self.get_json(&format!("/preapproval/{}", id))
+                    .execute()
+                    .await?,
+            ),
+            WebhookTopic::SubscriptionAuthorizedPayment => {
+                WebhookResource::SubscriptionAuthorizedPayment(
+                    self.get_json(&format!("/authorized_payments/{}", id))
+                        .execute()
+                        .await?,
+                )
+            }
+            WebhookTopic::PointIntegrationWh => WebhookResource::PointPaymentIntent(
+                self.get_json(&format!("/point/integration-api/payment-intents/{}", id))
+                    .execute()
+                    .await?,
+            ),
+            WebhookTopic::TopicChargebacksWh => WebhookResource::Chargeback(
+                self.get_json(&format!("/v1/chargebacks/{}", id))
+                    .execute()
+                    .await?,
+            ),
+            other => return Err(WebhookError::UnsupportedTopic(other.to_string()).into()),
+        };
+
+        Ok(resource)
+    }
+
+    /// Fetches a resource that is not modelled by this crate.
+    fn get_json(&self, path: &str) -> SDKRequest<'_, serde_json::Value> {
+        self.sdk_request(self.http_client.request(Method::GET, self.url(path)))
     }
 }
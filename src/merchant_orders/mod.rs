@@ -0,0 +1,4 @@
+//! Merchant orders group every payment made for a Checkout Pro preference, along with its items
+//! and shipments.
+
+pub mod responses;
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common_types::{CurrencyId, Item};
+use crate::payments::responses::{PaymentStatus, StatusDetail};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MerchantOrderResponse {
+    pub id: i64,
+
+    /// Either `opened` or `closed`.
+    pub status: String,
+
+    /// Payment situation of the order, such as `payment_required`, `partially_paid`, `paid`
+    /// or `reverted`.
+    pub order_status: Option<String>,
+
+    pub external_reference: Option<String>,
+    pub preference_id: Option<String>,
+
+    #[serde(default)]
+    pub payments: Vec<MerchantOrderPayment>,
+    #[serde(default)]
+    pub items: Vec<Item>,
+
+    pub total_amount: f64,
+    #[serde(default)]
+    pub paid_amount: f64,
+    #[serde(default)]
+    pub refunded_amount: f64,
+    #[serde(default)]
+    pub shipping_cost: f64,
+
+    pub notification_url: Option<String>,
+    pub site_id: Option<String>,
+    #[serde(default)]
+    pub cancelled: bool,
+    #[serde(default)]
+    pub is_test: bool,
+
+    #[serde(with = "time::serde::rfc3339")]
+    pub date_created: time::OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub last_updated: time::OffsetDateTime,
+}
+
+/// Summary of a payment made for a merchant order. Use
+/// [`MercadoPagoSDK::get_payment`](crate::MercadoPagoSDK::get_payment) for its details.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MerchantOrderPayment {
+    pub id: i64,
+    pub transaction_amount: f64,
+    #[serde(default)]
+    pub total_paid_amount: f64,
+    #[serde(default)]
+    pub amount_refunded: f64,
+    pub currency_id: Option<CurrencyId>,
+    pub status: PaymentStatus,
+    pub status_detail: StatusDetail,
+    pub date_approved: Option<String>,
+    pub date_created: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_deserialization() {
+        let response = serde_json::from_slice::<MerchantOrderResponse>(include_bytes!(
+            "../../tests/assets/merchant_order_response.json"
+        ));
+
+        assert!(response.is_ok());
+    }
+}
@@ -0,0 +1,35 @@
+//! Exchange of application credentials for an access token, through
+//! [`MercadoPagoSDKBuilder::authorize`](crate::MercadoPagoSDKBuilder::authorize).
+
+use serde::{Deserialize, Serialize};
+
+use crate::sensitive::SensitiveString;
+
+/// Credentials of an application, shown on its MercadoPago developer panel.
+#[derive(Debug, Clone)]
+pub(crate) struct ClientCredentials {
+    pub(crate) client_id: String,
+    pub(crate) client_secret: SensitiveString,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ClientCredentialsPayload<'a> {
+    pub(crate) client_id: &'a str,
+    pub(crate) client_secret: &'a SensitiveString,
+    pub(crate) grant_type: &'static str,
+}
+
+impl<'a> From<&'a ClientCredentials> for ClientCredentialsPayload<'a> {
+    fn from(credentials: &'a ClientCredentials) -> Self {
+        Self {
+            client_id: &credentials.client_id,
+            client_secret: &credentials.client_secret,
+            grant_type: "client_credentials",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct OAuthTokenResponse {
+    pub(crate) access_token: SensitiveString,
+}
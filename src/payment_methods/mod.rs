@@ -0,0 +1,7 @@
+//! Payment methods available to your account, along with the rules to validate cards of each
+//! brand, such as BIN patterns and security code length.
+//!
+//! Official API page: https://www.mercadopago.com.br/developers/en/reference/payment_methods/_payment_methods/get
+
+pub mod requests;
+pub mod responses;
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+use crate::payments::requests::PaymentMethodId;
+
+/// Query of [`MercadoPagoSDK::get_installments`](crate::MercadoPagoSDK::get_installments).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstallmentsQuery {
+    pub amount: f64,
+
+    /// First 6 or 8 digits of the card.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bin: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_method_id: Option<PaymentMethodId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issuer_id: Option<i64>,
+}
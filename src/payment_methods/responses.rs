@@ -0,0 +1,177 @@
+use serde::{Deserialize, Serialize};
+
+use crate::helpers::string_enum;
+use crate::payments::requests::{PaymentMethodId, PaymentTypeId};
+
+/// An entry of [`MercadoPagoSDK::get_payment_methods`](crate::MercadoPagoSDK::get_payment_methods).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaymentMethodInfo {
+    pub id: PaymentMethodId,
+    pub name: String,
+    pub payment_type_id: PaymentTypeId,
+    pub status: PaymentMethodStatus,
+
+    pub thumbnail: Option<String>,
+    pub secure_thumbnail: Option<String>,
+
+    /// Whether payments can be authorized and captured later: `supported`, `unsupported` or
+    /// `does_not_apply`.
+    pub deferred_capture: Option<String>,
+
+    /// Validation rules of cards. Empty for methods other than cards.
+    #[serde(default)]
+    pub settings: Vec<PaymentMethodSettings>,
+    /// Fields which must be sent along payments with this method, such as
+    /// `cardholder_identification_number`.
+    #[serde(default)]
+    pub additional_info_needed: Vec<String>,
+
+    pub min_allowed_amount: f64,
+    pub max_allowed_amount: f64,
+
+    /// Minutes until the money of an approved payment is available.
+    pub accreditation_time: i64,
+
+    #[serde(default)]
+    pub financial_institutions: Vec<FinancialInstitution>,
+    #[serde(default)]
+    pub processing_modes: Vec<String>,
+}
+
+string_enum! {
+    pub enum PaymentMethodStatus {
+        Active = "active",
+        Deactive = "deactive",
+        /// Temporarily unavailable, such as during a brand outage.
+        TemporallyDeactive = "temporally_deactive",
+    }
+}
+
+/// Rules a card must follow to be accepted by a payment method.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaymentMethodSettings {
+    pub card_number: CardNumberSettings,
+    pub bin: BinSettings,
+    pub security_code: SecurityCodeSettings,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CardNumberSettings {
+    /// Check applied to card numbers, such as `standard` for the Luhn algorithm, or `none`.
+    pub validation: String,
+    pub length: i64,
+}
+
+/// Regular expressions matched against the first digits (BIN) of cards.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BinSettings {
+    /// Cards of this method.
+    pub pattern: String,
+    /// Cards which can be paid in installments.
+    pub installments_pattern: Option<String>,
+    /// Cards which are not of this method, despite matching `pattern`.
+    pub exclusion_pattern: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SecurityCodeSettings {
+    pub length: i64,
+    /// Where the security code is printed, such as `back` or `front`.
+    pub card_location: String,
+    /// Whether it is `mandatory` or `optional`.
+    pub mode: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FinancialInstitution {
+    pub id: String,
+    pub description: String,
+}
+
+/// Installment plans of a card, an entry of
+/// [`MercadoPagoSDK::get_installments`](crate::MercadoPagoSDK::get_installments).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstallmentOptions {
+    pub payment_method_id: PaymentMethodId,
+    pub payment_type_id: PaymentTypeId,
+    pub issuer: Option<InstallmentsIssuer>,
+    pub processing_mode: Option<String>,
+    pub payer_costs: Vec<PayerCost>,
+}
+
+impl InstallmentOptions {
+    /// The plan of `installments`, if offered. Payments with any other number of installments
+    /// are rejected.
+    pub fn payer_cost(&self, installments: i64) -> Option<&PayerCost> {
+        self.payer_costs
+            .iter()
+            .find(|cost| cost.installments == installments)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstallmentsIssuer {
+    /// Sent as `issuer_id` when creating payments.
+    #[serde(deserialize_with = "serde_aux::field_attributes::deserialize_number_from_string")]
+    pub id: i64,
+    pub name: String,
+    pub thumbnail: Option<String>,
+    pub secure_thumbnail: Option<String>,
+}
+
+/// An installment plan, and how much the buyer pays with it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PayerCost {
+    pub installments: i64,
+    /// Interest rate, as a percentage of the amount.
+    pub installment_rate: f64,
+    pub installment_amount: f64,
+    pub total_amount: f64,
+
+    pub min_allowed_amount: f64,
+    pub max_allowed_amount: f64,
+
+    /// Tags such as `recommended_installment`, or the CFT and TEA rates shown in Brazil.
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Message to be shown to the buyer, such as `3 parcelas de R$ 36,15 (R$ 108,45)`.
+    pub recommended_message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_deserialization() {
+        let methods = serde_json::from_slice::<Vec<PaymentMethodInfo>>(include_bytes!(
+            "../../tests/assets/payment_methods_response.json"
+        ))
+        .unwrap();
+
+        let amex = &methods[0];
+        assert_eq!(amex.id, PaymentMethodId::Amex);
+        assert_eq!(amex.payment_type_id, PaymentTypeId::CreditCard);
+        assert_eq!(amex.settings[0].card_number.length, 15);
+        assert_eq!(amex.settings[0].security_code.card_location, "front");
+
+        let pix = methods
+            .iter()
+            .find(|m| m.id == PaymentMethodId::Pix)
+            .unwrap();
+        assert_eq!(pix.payment_type_id, PaymentTypeId::BankTransfer);
+        assert!(pix.settings.is_empty());
+    }
+
+    #[test]
+    fn t_installments_deserialization() {
+        let options = serde_json::from_slice::<Vec<InstallmentOptions>>(include_bytes!(
+            "../../tests/assets/installments_response.json"
+        ))
+        .unwrap();
+
+        assert_eq!(options[0].issuer.as_ref().unwrap().id, 25);
+        assert_eq!(options[0].payer_cost(3).unwrap().total_amount, 108.45);
+        assert!(options[0].payer_cost(12).is_none());
+    }
+}
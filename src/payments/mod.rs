@@ -4,5 +4,6 @@
 //! You will be always handling tokens instead of the raw credit/debit card information. You can
 //! store it on your database if you want to.
 
+pub mod pix;
 pub mod requests;
 pub mod responses;
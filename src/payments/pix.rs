@@ -0,0 +1,242 @@
+//! Pix, the Brazilian instant payment method, paid by scanning a QR code or pasting its code on
+//! the buyer's bank app.
+//!
+//! Build charges with [`PixPayment`], and show the code to the buyer through
+//! [`CreatePaymentResponse::pix_qr_code`].
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use time::format_description::well_known::Rfc3339;
+use time::format_description::BorrowedFormatItem;
+use time::macros::format_description;
+use time::OffsetDateTime;
+
+use crate::common_types::PersonalIdentification;
+use crate::errors::{SDKError, ValidationError};
+use crate::payments::requests::{
+    BuyerIdentification, BuyerType, CreatePaymentPayload, DocumentType, PaymentMethodId,
+};
+use crate::payments::responses::CreatePaymentResponse;
+
+/// MercadoPago only accepts expiration dates with milliseconds, such as
+/// `2026-01-31T23:59:59.000-03:00`.
+const DATE_OF_EXPIRATION_FORMAT: &[BorrowedFormatItem<'_>] = format_description!(
+    "[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond digits:3][offset_hour \
+     sign:mandatory]:[offset_minute]"
+);
+
+/// Builds the [`CreatePaymentPayload`] of a Pix charge.
+///
+/// ```
+/// use mercadopago_sdk_rust::common_types::PersonalIdentification;
+/// use mercadopago_sdk_rust::payments::pix::PixPayment;
+/// use mercadopago_sdk_rust::payments::requests::DocumentType;
+///
+/// let payer = PersonalIdentification::new(DocumentType::CPF, "19119119100").unwrap();
+/// let payload = PixPayment::new(100.0, "Order #123", "buyer@example.com", payer)
+///     .unwrap()
+///     .set_external_reference("123")
+///     .build();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PixPayment {
+    amount: f64,
+    description: String,
+    payer_email: String,
+    payer_identification: PersonalIdentification,
+    payer_first_name: Option<String>,
+    payer_last_name: Option<String>,
+    date_of_expiration: Option<OffsetDateTime>,
+    external_reference: Option<String>,
+}
+
+impl PixPayment {
+    /// Pix charges need a positive amount, and a payer identified by CPF or CNPJ.
+    pub fn new<T: ToString, E: ToString>(
+        amount: f64,
+        description: T,
+        payer_email: E,
+        payer_identification: PersonalIdentification,
+    ) -> Result<Self, SDKError> {
+        if amount <= 0.0 || !amount.is_finite() {
+            return Err(ValidationError::PaymentError(format!(
+                "Pix payments need a positive amount, got {}.",
+                amount
+            ))
+            .into());
+        }
+
+        if !matches!(
+            payer_identification.document_type,
+            Some(DocumentType::CPF | DocumentType::CNPJ)
+        ) || payer_identification.number.is_none()
+        {
+            return Err(ValidationError::PayerError(
+                "Pix payers must be identified by CPF or CNPJ.".to_string(),
+            )
+            .into());
+        }
+
+        Ok(Self {
+            amount,
+            description: description.to_string(),
+            payer_email: payer_email.to_string(),
+            payer_identification,
+            payer_first_name: None,
+            payer_last_name: None,
+            date_of_expiration: None,
+            external_reference: None,
+        })
+    }
+
+    pub fn set_payer_name<T: ToString>(mut self, first_name: T, last_name: T) -> Self {
+        self.payer_first_name = Some(first_name.to_string());
+        self.payer_last_name = Some(last_name.to_string());
+        self
+    }
+
+    /// Sets until when the charge can be paid, between 30 minutes and 30 days from its creation.
+    /// MercadoPago defaults to 24 hours.
+    pub fn set_date_of_expiration(mut self, date_of_expiration: OffsetDateTime) -> Self {
+        self.date_of_expiration = Some(date_of_expiration);
+        self
+    }
+
+    pub fn set_external_reference<T: ToString>(mut self, external_reference: T) -> Self {
+        self.external_reference = Some(external_reference.to_string());
+        self
+    }
+
+    pub fn build(self) -> CreatePaymentPayload {
+        let date_of_expiration = self
+            .date_of_expiration
+            .and_then(|date| date.format(DATE_OF_EXPIRATION_FORMAT).ok());
+
+        CreatePaymentPayload {
+            description: self.description,
+            installments: 1,
+            order: None,
+            payer: BuyerIdentification {
+                email: Some(self.payer_email),
+                buyer_type: BuyerType::Guest,
+                buyer_entity_type: None,
+                identification: Some(self.payer_identification),
+                first_name: self.payer_first_name,
+                last_name: self.payer_last_name,
+            },
+            payment_method_id: PaymentMethodId::Pix.to_string(),
+            token: None,
+            transaction_amount: self.amount,
+            external_reference: self.external_reference,
+            binary_mode: None,
+            capture: None,
+            date_of_expiration,
+            statement_descriptor: None,
+            additional_info: None,
+            metadata: None,
+        }
+    }
+}
+
+/// What the buyer needs to pay a Pix charge.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PixQrCode {
+    /// "Pix copia e cola" code, pasted by the buyer on their bank app.
+    pub code: String,
+    /// The QR code, as a PNG image.
+    pub png: Vec<u8>,
+    /// MercadoPago page showing the QR code and payment instructions.
+    pub ticket_url: Option<String>,
+    /// Until when the charge can be paid.
+    pub expires_at: Option<OffsetDateTime>,
+}
+
+impl CreatePaymentResponse {
+    /// The QR code of a Pix payment. `None` for other payment methods, or when MercadoPago sent
+    /// no QR code, or an image which is not valid base64.
+    pub fn pix_qr_code(&self) -> Option<PixQrCode> {
+        let transaction_data = self
+            .point_of_interaction
+            .as_ref()?
+            .transaction_data
+            .as_ref()?;
+
+        Some(PixQrCode {
+            code: transaction_data.qr_code.clone()?,
+            png: STANDARD
+                .decode(transaction_data.qr_code_base64.as_ref()?)
+                .ok()?,
+            ticket_url: transaction_data.ticket_url.clone(),
+            expires_at: self
+                .date_of_expiration
+                .as_deref()
+                .and_then(|date| OffsetDateTime::parse(date, &Rfc3339).ok()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    fn payer() -> PersonalIdentification {
+        PersonalIdentification::new(DocumentType::CPF, "19119119100").unwrap()
+    }
+
+    #[test]
+    fn t_payload() {
+        let payload = PixPayment::new(10.5, "Order #123", "buyer@example.com", payer())
+            .unwrap()
+            .set_date_of_expiration(datetime!(2026-10-19 12:00 -3))
+            .build();
+
+        assert_eq!(
+            serde_json::to_value(payload).unwrap(),
+            serde_json::json!({
+                "description": "Order #123",
+                "installments": 1,
+                "payer": {
+                    "email": "buyer@example.com",
+                    "type": "guest",
+                    "entity_type": null,
+                    "identification": {"type": "CPF", "number": "19119119100"},
+                    "first_name": null,
+                    "last_name": null
+                },
+                "payment_method_id": "pix",
+                "transaction_amount": 10.5,
+                "date_of_expiration": "2026-10-19T12:00:00.000-03:00"
+            })
+        );
+    }
+
+    #[test]
+    fn t_invalid_payment() {
+        assert!(matches!(
+            PixPayment::new(0.0, "Order #123", "buyer@example.com", payer()),
+            Err(SDKError::ValidationError(ValidationError::PaymentError(_)))
+        ));
+
+        let dni = PersonalIdentification::new(DocumentType::DNI, "12345678").unwrap();
+        assert!(matches!(
+            PixPayment::new(10.0, "Order #123", "buyer@example.com", dni),
+            Err(SDKError::ValidationError(ValidationError::PayerError(_)))
+        ));
+    }
+
+    #[test]
+    fn t_qr_code() {
+        let response = serde_json::from_slice::<CreatePaymentResponse>(include_bytes!(
+            "../../tests/assets/pix_payment_response.json"
+        ))
+        .unwrap();
+
+        let qr_code = response.pix_qr_code().unwrap();
+        assert!(qr_code.code.starts_with("00020126600014br.gov.bcb.pix"));
+        assert!(qr_code.png.starts_with(b"\x89PNG"));
+        assert_eq!(qr_code.expires_at, Some(datetime!(2026-10-19 12:00 -3)));
+        assert!(qr_code.ticket_url.is_some());
+    }
+}
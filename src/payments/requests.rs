@@ -1,6 +1,10 @@
 use serde::{Deserialize, Serialize};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
 
 use crate::common_types::{Address, Item, PersonalIdentification, Phone, Shipments};
+use crate::helpers::string_enum;
+use crate::payments::responses::PaymentStatus;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CreatePaymentPayload {
@@ -10,7 +14,8 @@ pub struct CreatePaymentPayload {
     /// Required.
     pub installments: i64,
 
-    pub order: Order,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<Order>,
     pub payer: BuyerIdentification,
 
     /// Required.
@@ -18,22 +23,39 @@ pub struct CreatePaymentPayload {
 
     /// Use `MercadoPagoSDK::create_card_token` to generate one.
     /// Required for credit card payments.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub token: Option<String>,
 
     /// Total amount of the transaction
     /// Required.
     pub transaction_amount: f64,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub external_reference: Option<String>,
 
     /// Defaults to false. When set to true, payments can only be approved or rejected instantly.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub binary_mode: Option<bool>,
 
+    /// Defaults to true. When set to false, card payments are only authorized, reserving the
+    /// amount until captured with `MercadoPagoSDK::capture_payment`, or released with
+    /// `MercadoPagoSDK::cancel_payment`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capture: Option<bool>,
+
+    /// Until when a pending payment, such as a Pix or a ticket, can be paid. Formatted as
+    /// `2026-10-19T09:37:52.000-03:00`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_of_expiration: Option<String>,
+
     /// Description that the payment will appear with in the card statement.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub statement_descriptor: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub additional_info: Option<AdditionalInfo>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<serde_json::Value>,
 }
 
@@ -91,61 +113,243 @@ pub struct BuyerIdentification {
     pub last_name: Option<String>,
 }
 
-#[derive(
-    Copy, Clone, Deserialize, Serialize, PartialEq, Debug, strum::IntoStaticStr, strum::AsRefStr,
-)]
-#[serde(rename_all = "snake_case")]
-pub enum PaymentTypeId {
-    CreditCard,
-    DebitCard,
+string_enum! {
+    pub enum PaymentTypeId {
+        CreditCard = "credit_card",
+        DebitCard = "debit_card",
+        PrepaidCard = "prepaid_card",
+
+        /// Instant Money transfer methods
+        BankTransfer = "bank_transfer",
+
+        /// Pay later methods, such as boleto, lotérica or another local payment
+        Ticket = "ticket",
 
-    /// Instant Money transfer methods
-    BankTransfer,
+        /// Payment at an ATM
+        Atm = "atm",
 
-    /// Pay later methods, such as boleto, lotérica or another local payment
-    Ticket,
+        /// MercadoPago Account
+        AccountMoney = "account_money",
 
-    /// MercadoPago Account
-    AccountMoney,
+        DigitalCurrency = "digital_currency",
+        DigitalWallet = "digital_wallet",
+        VoucherCard = "voucher_card",
+    }
 }
 
-#[derive(
-    Copy, Clone, Deserialize, Serialize, PartialEq, Debug, strum::IntoStaticStr, strum::AsRefStr,
-)]
-#[serde(rename_all = "lowercase")]
-pub enum PaymentMethodId {
-    Amex,
-    Visa,
-    Master,
-    Hipercard,
+string_enum! {
+    /// Payment methods of every country. The ones available to your account are listed by
+    /// [`MercadoPagoSDK::get_payment_methods`](crate::MercadoPagoSDK::get_payment_methods).
+    pub enum PaymentMethodId {
+        Amex = "amex",
+        Visa = "visa",
+        Master = "master",
+        Hipercard = "hipercard",
+
+        /// Crédito Elo
+        Elo = "elo",
+        Naranja = "naranja",
+        Cabal = "cabal",
+        Diners = "diners",
+        Cencosud = "cencosud",
+        Argencard = "argencard",
+        Tarshop = "tarshop",
+        Cmr = "cmr",
+
+        /// Débito Elo
+        Debelo = "debelo",
+        Debvisa = "debvisa",
+        Debmaster = "debmaster",
+        Debcabal = "debcabal",
+        Maestro = "maestro",
+
+        /// Pagamento na lotérica sem boleto
+        Pec = "pec",
+
+        Pix = "pix",
+
+        /// Boleto Bradesco
+        BolBradesco = "bolbradesco",
+
+        Rapipago = "rapipago",
+        Pagofacil = "pagofacil",
+        Oxxo = "oxxo",
+        Pse = "pse",
+        Efecty = "efecty",
+
+        /// Dinheiro na conta MercadoPago
+        AccountMoney = "account_money",
+    }
+}
 
-    /// Pagamento na lotérica sem boleto
-    Pec,
+string_enum! {
+    /// Type of a [`PersonalIdentification`]. The ones of your country are listed by
+    /// [`MercadoPagoSDK::get_identification_types`](crate::MercadoPagoSDK::get_identification_types).
+    pub enum DocumentType {
+        /// Brazilian individuals.
+        CPF = "CPF",
+        /// Brazilian companies.
+        CNPJ = "CNPJ",
+
+        /// Argentine and Peruvian national identity document.
+        DNI = "DNI",
+        /// Argentine taxpayer identification, of companies and individuals.
+        CUIT = "CUIT",
+        /// Argentine labor identification.
+        CUIL = "CUIL",
+
+        /// Chilean national identification, with a check digit which may be a `K`.
+        RUT = "RUT",
+
+        /// Uruguayan identity card.
+        CI = "CI",
+
+        /// Mexican taxpayer identification.
+        RFC = "RFC",
+        /// Mexican population registry key.
+        CURP = "CURP",
+
+        /// Colombian citizenship card.
+        CC = "CC",
+        /// Colombian and Peruvian foreigner card.
+        CE = "CE",
+        /// Colombian taxpayer identification.
+        NIT = "NIT",
+
+        /// Peruvian taxpayer identification.
+        RUC = "RUC",
+
+        _ => Other,
+    }
+}
 
-    Pix,
+/// Fields that can be changed on an existing payment, through
+/// [`MercadoPagoSDK::update_payment`](crate::MercadoPagoSDK::update_payment).
+///
+/// Only the fields that are set will be sent.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpdatePaymentPayload {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<PaymentStatus>,
 
-    /// Boleto Bradesco
-    BolBradesco,
+    /// Captures an authorized payment when set to true.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capture: Option<bool>,
 
-    /// Dinheiro na conta MercadoPago
-    AccountMoney,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_amount: Option<f64>,
 
-    /// Débito Elo
-    Debelo,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_of_expiration: Option<String>,
 
-    /// Crédito Elo
-    Elo,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_reference: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
 }
 
-#[derive(
-    Copy, Clone, Deserialize, Serialize, PartialEq, Debug, strum::IntoStaticStr, strum::AsRefStr,
-)]
-pub enum DocumentType {
-    CPF,
-    CNPJ,
+/// Date field used by [`PaymentSearchOptions::set_date_range`].
+#[derive(Copy, Clone, Deserialize, Serialize, PartialEq, Debug, strum::AsRefStr)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum PaymentDateField {
+    DateCreated,
+    DateApproved,
+    DateLastUpdated,
+    MoneyReleaseDate,
+}
 
-    /// Argentina? Not sure since MercadoPago doesn't show from other countries (need account)
-    DNI,
+/// Field used to sort [`PaymentSearchOptions`] results.
+#[derive(Copy, Clone, Deserialize, Serialize, PartialEq, Debug, strum::AsRefStr)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum PaymentSortField {
+    DateCreated,
+    DateApproved,
+    DateLastUpdated,
+    MoneyReleaseDate,
+    Id,
+}
+
+#[derive(Copy, Clone, Deserialize, Serialize, PartialEq, Debug, strum::AsRefStr)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum SortCriteria {
+    Asc,
+    Desc,
+}
+
+/// Filters for [`MercadoPagoSDK::search_payments`](crate::MercadoPagoSDK::search_payments), sent
+/// as query parameters.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaymentSearchOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) status: Option<PaymentStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) external_reference: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) sort: Option<PaymentSortField>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) criteria: Option<SortCriteria>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) range: Option<PaymentDateField>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) begin_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) end_date: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) offset: Option<u32>,
+}
+
+impl PaymentSearchOptions {
+    /// Creates blank filters, which match every payment.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only payments with `status`.
+    pub fn set_status(mut self, status: PaymentStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    pub fn set_external_reference<T: ToString>(mut self, external_reference: T) -> Self {
+        self.external_reference = Some(external_reference.to_string());
+        self
+    }
+
+    /// Only payments whose `field` is between `begin` and `end`.
+    pub fn set_date_range(
+        mut self,
+        field: PaymentDateField,
+        begin: OffsetDateTime,
+        end: OffsetDateTime,
+    ) -> Self {
+        self.range = Some(field);
+        self.begin_date = begin.format(&Rfc3339).ok();
+        self.end_date = end.format(&Rfc3339).ok();
+        self
+    }
+
+    pub fn set_sort(mut self, sort: PaymentSortField, criteria: SortCriteria) -> Self {
+        self.sort = Some(sort);
+        self.criteria = Some(criteria);
+        self
+    }
+
+    /// Sets the page to be returned. MercadoPago defaults to a `limit` of 30.
+    pub fn set_pagination(mut self, limit: u32, offset: u32) -> Self {
+        self.limit = Some(limit);
+        self.offset = Some(offset);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -1,51 +1,173 @@
 use serde::{Deserialize, Serialize};
 
 use crate::common_types::{Card, CurrencyId};
+use crate::helpers::string_enum;
 use crate::payments::requests::{AdditionalInfo, BuyerIdentification};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CreatePaymentResponse {
     pub additional_info: Option<AdditionalInfo>,
-    pub card: Card,
+    /// Only present on card payments.
+    pub card: Option<Card>,
     pub collector_id: i64,
-    pub coupon_amount: i64,
+    #[serde(default)]
+    pub coupon_amount: f64,
     pub currency_id: CurrencyId,
-    pub date_approved: String,
+    /// Null until the payment is approved.
+    pub date_approved: Option<String>,
     pub date_created: String,
     pub date_last_updated: String,
-    pub description: String,
-    pub external_reference: String,
+    /// Until when a pending payment, such as a Pix or a ticket, can be paid.
+    pub date_of_expiration: Option<String>,
+    pub description: Option<String>,
+    pub external_reference: Option<String>,
+    #[serde(default)]
     pub fee_details: Vec<FeeDetail>,
     pub id: i64,
     pub installments: i64,
-    pub issuer_id: i64,
+    #[serde(
+        default,
+        deserialize_with = "serde_aux::field_attributes::deserialize_option_number_from_string"
+    )]
+    pub issuer_id: Option<i64>,
     pub metadata: Option<serde_json::Value>,
-    pub money_release_date: String,
-    pub notification_url: String,
+    pub money_release_date: Option<String>,
+    pub notification_url: Option<String>,
+    #[serde(default)]
     pub order: Order,
     pub payer: BuyerIdentification,
     pub payment_method_id: String,
     pub payment_type_id: String,
-    pub point_of_interaction: PointOfInteraction,
-    pub processing_mode: String,
-    pub shipping_amount: i64,
-    pub statement_descriptor: String,
-    pub status: String,
-    pub status_detail: String,
-    pub taxes_amount: i64,
+    pub point_of_interaction: Option<PointOfInteraction>,
+    pub processing_mode: Option<String>,
+    #[serde(default)]
+    pub shipping_amount: f64,
+    pub statement_descriptor: Option<String>,
+    pub status: PaymentStatus,
+    pub status_detail: StatusDetail,
+    #[serde(default)]
+    pub taxes_amount: f64,
     pub transaction_amount: f64,
-    pub transaction_amount_refunded: i64,
+    #[serde(default)]
+    pub transaction_amount_refunded: f64,
+    #[serde(default)]
     pub transaction_details: TransactionDetails,
 }
 
+string_enum! {
+    /// Status of a payment.
+    pub enum PaymentStatus {
+        /// The buyer has not finished paying yet, such as an unpaid ticket or Pix.
+        Pending = "pending",
+        Approved = "approved",
+        /// The amount is reserved on the card, waiting to be captured.
+        Authorized = "authorized",
+        /// The payment is being reviewed.
+        InProcess = "in_process",
+        /// The buyer started a dispute.
+        InMediation = "in_mediation",
+        Rejected = "rejected",
+        /// The payment was cancelled by one of the parties, or expired.
+        Cancelled = "cancelled",
+        Refunded = "refunded",
+        /// A chargeback was made on the buyer's card.
+        ChargedBack = "charged_back",
+    }
+}
+
+impl PaymentStatus {
+    /// Whether the money was received.
+    pub fn is_success(&self) -> bool {
+        matches!(self, Self::Approved)
+    }
+
+    /// Whether the payment will not change status anymore.
+    ///
+    /// Approved payments are not final, since they can still be refunded or charged back.
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            Self::Rejected | Self::Cancelled | Self::Refunded | Self::ChargedBack
+        )
+    }
+}
+
+string_enum! {
+    /// Reason of the current [`PaymentStatus`].
+    pub enum StatusDetail {
+        Accredited = "accredited",
+        PartiallyRefunded = "partially_refunded",
+        PendingCapture = "pending_capture",
+        PendingContingency = "pending_contingency",
+        PendingReviewManual = "pending_review_manual",
+        PendingWaitingPayment = "pending_waiting_payment",
+        PendingWaitingTransfer = "pending_waiting_transfer",
+        PendingChallenge = "pending_challenge",
+        CcRejected3dsMandatory = "cc_rejected_3ds_mandatory",
+        CcRejectedBadFilledCardNumber = "cc_rejected_bad_filled_card_number",
+        CcRejectedBadFilledDate = "cc_rejected_bad_filled_date",
+        CcRejectedBadFilledOther = "cc_rejected_bad_filled_other",
+        CcRejectedBadFilledSecurityCode = "cc_rejected_bad_filled_security_code",
+        CcRejectedBlacklist = "cc_rejected_blacklist",
+        CcRejectedCallForAuthorize = "cc_rejected_call_for_authorize",
+        CcRejectedCardDisabled = "cc_rejected_card_disabled",
+        CcRejectedCardError = "cc_rejected_card_error",
+        CcRejectedCardTypeNotAllowed = "cc_rejected_card_type_not_allowed",
+        CcRejectedDuplicatedPayment = "cc_rejected_duplicated_payment",
+        CcRejectedHighRisk = "cc_rejected_high_risk",
+        CcRejectedInsufficientAmount = "cc_rejected_insufficient_amount",
+        CcRejectedInvalidInstallments = "cc_rejected_invalid_installments",
+        CcRejectedMaxAttempts = "cc_rejected_max_attempts",
+        CcRejectedOtherReason = "cc_rejected_other_reason",
+        RejectedByBank = "rejected_by_bank",
+        RejectedByRegulations = "rejected_by_regulations",
+        RejectedInsufficientData = "rejected_insufficient_data",
+        /// Cancelled because it was not paid in time.
+        Expired = "expired",
+        ByCollector = "by_collector",
+        ByPayer = "by_payer",
+        Refunded = "refunded",
+        InProcess = "in_process",
+        Settled = "settled",
+        Reimbursed = "reimbursed",
+    }
+}
+
+impl StatusDetail {
+    /// Whether the card was rejected, which may be fixed by the buyer, such as by retyping the
+    /// security code, or by paying with another card.
+    pub fn is_card_rejection(&self) -> bool {
+        self.as_str().starts_with("cc_rejected_")
+    }
+}
+
+/// Result of [`MercadoPagoSDK::search_payments`](crate::MercadoPagoSDK::search_payments).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaymentSearchResponse {
+    pub paging: Paging,
+    pub results: Vec<CreatePaymentResponse>,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Paging {
+    /// Total of payments matching the filters, across every page.
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Order {}
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TransactionDetails {
+    #[serde(default)]
     pub net_received_amount: f64,
+    #[serde(default)]
     pub total_paid_amount: f64,
-    pub overpaid_amount: i64,
+    #[serde(default)]
+    pub overpaid_amount: f64,
+    #[serde(default)]
     pub installment_amount: f64,
 }
 
@@ -61,22 +183,23 @@ pub struct FeeDetail {
 pub struct PointOfInteraction {
     #[serde(rename = "type")]
     pub type_field: String,
-    pub application_data: ApplicationData,
-    pub transaction_data: TransactionData,
+    pub application_data: Option<ApplicationData>,
+    pub transaction_data: Option<TransactionData>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ApplicationData {
-    pub name: String,
-    pub version: String,
+    pub name: Option<String>,
+    pub version: Option<String>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TransactionData {
-    pub qr_code_base64: String,
-    pub qr_code: String,
-    pub ticket_url: String,
+    pub qr_code_base64: Option<String>,
+    pub qr_code: Option<String>,
+    pub ticket_url: Option<String>,
 }
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,4 +212,35 @@ mod tests {
         .unwrap();
         println!("{:?}", payload);
     }
+
+    #[test]
+    fn t_status_deserialization() {
+        let status = serde_json::from_str::<PaymentStatus>(r#""charged_back""#).unwrap();
+        assert_eq!(status, PaymentStatus::ChargedBack);
+        assert!(status.is_final());
+        assert!(!status.is_success());
+
+        let detail = serde_json::from_str::<StatusDetail>(r#""cc_rejected_new_reason""#).unwrap();
+        assert_eq!(
+            detail,
+            StatusDetail::Unknown("cc_rejected_new_reason".to_string())
+        );
+        assert!(detail.is_card_rejection());
+        assert_eq!(
+            serde_json::to_string(&detail).unwrap(),
+            r#""cc_rejected_new_reason""#
+        );
+    }
+
+    #[test]
+    fn t_search_deserialization() {
+        let payload = serde_json::from_str::<PaymentSearchResponse>(&format!(
+            r#"{{"paging": {{"total": 1, "limit": 30, "offset": 0}}, "results": [{}]}}"#,
+            include_str!("../../tests/assets/create_payment_response.json")
+        ))
+        .unwrap();
+
+        assert_eq!(payload.paging.total, 1);
+        assert_eq!(payload.results[0].id, 20359978);
+    }
 }
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
 use crate::common_types::{BackUrls, CheckoutProPayer, Item, PaymentMethods};
+use crate::identification_types::validation;
 
 /// Buyers will be redirected back to your site immediately after completing
 /// the purchase.
@@ -95,7 +96,7 @@ impl Default for CheckoutProPreferences {
 
 impl CheckoutProPreferences {
     /// Creates a blank [`CheckoutProPreferences`].
-    /// You need to set at least a `Payer`, and one `Item`, otherwise it will fail to validate.
+    /// You need to set at least one `Item`.
     #[must_use]
     pub fn new() -> Self {
         Self::default()
@@ -146,10 +147,16 @@ impl CheckoutProPreferences {
         self
     }
 
+    /// MercadoPago doesn't require a payer, so only an identification that was set is checked.
     pub(crate) fn validate(&self) -> bool {
-        if !self.payer.validate() {
-            return false;
+        match (
+            &self.payer.identification.document_type,
+            &self.payer.identification.number,
+        ) {
+            (Some(document_type), Some(number)) => {
+                validation::validate(document_type, number).is_ok()
+            }
+            _ => true,
         }
-        true
     }
 }
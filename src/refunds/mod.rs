@@ -0,0 +1,7 @@
+//! Refunds return the money of a payment to the buyer, either fully or partially.
+//!
+//! A payment can be partially refunded many times, until the sum of its refunds reaches the
+//! transaction amount.
+
+pub mod requests;
+pub mod responses;
@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+/// Payload of [`MercadoPagoSDK::create_refund`](crate::MercadoPagoSDK::create_refund).
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateRefundPayload {
+    /// Amount to be refunded. When absent, the whole payment is refunded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<f64>,
+}
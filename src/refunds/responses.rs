@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RefundResponse {
+    pub id: i64,
+    pub payment_id: i64,
+    pub amount: f64,
+
+    /// Status of the refund, such as `approved`, `in_process` or `rejected`.
+    pub status: String,
+
+    /// Who issued the refund.
+    pub source: RefundSource,
+
+    #[serde(with = "time::serde::rfc3339")]
+    pub date_created: time::OffsetDateTime,
+
+    /// Number used by card brands to identify the refund. Only present after the refund is
+    /// processed by the card brand.
+    pub unique_sequence_number: Option<String>,
+
+    pub refund_mode: Option<String>,
+    pub metadata: Option<serde_json::Value>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RefundSource {
+    #[serde(deserialize_with = "serde_aux::field_attributes::deserialize_string_from_number")]
+    pub id: String,
+    pub name: Option<String>,
+
+    /// Such as `collector` or `admin`.
+    #[serde(rename = "type")]
+    pub type_field: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_refund_deserialization() {
+        let response = serde_json::from_slice::<RefundResponse>(include_bytes!(
+            "../../tests/assets/create_refund_response.json"
+        ));
+
+        assert!(response.is_ok());
+    }
+}
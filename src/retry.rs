@@ -0,0 +1,230 @@
+//! Retries of transient failures, configured through
+//! [`MercadoPagoSDKBuilder::set_retry_policy`](crate::MercadoPagoSDKBuilder::set_retry_policy).
+//!
+//! Only requests which are safe to send twice are retried: `GET`, `HEAD`, `PUT`, `DELETE` and
+//! `OPTIONS` requests, or any request carrying an idempotency key.
+
+use std::time::Duration;
+
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Method, StatusCode};
+use time::macros::format_description;
+use time::{OffsetDateTime, PrimitiveDateTime};
+
+use crate::errors::SDKError;
+use crate::transport::HttpResponse;
+
+/// How many times, and how often, a request is attempted on connection errors, `429 Too Many
+/// Requests` and `5xx` responses.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    pub(crate) max_attempts: u32,
+    pub(crate) base_delay: Duration,
+    pub(crate) max_delay: Duration,
+    pub(crate) jitter: bool,
+    pub(crate) respect_retry_after: bool,
+}
+
+/// Three attempts, starting at 200ms between them, with jitter, honoring `Retry-After`.
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(10),
+            jitter: true,
+            respect_retry_after: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Attempts each request up to `max_attempts` times, including the first one.
+    #[must_use]
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            ..Self::default()
+        }
+    }
+
+    /// Never retries. This is the default of [`crate::MercadoPagoSDK`].
+    #[must_use]
+    pub fn never() -> Self {
+        Self::new(1)
+    }
+
+    /// Sets the delay before the first retry, which doubles on each following one.
+    pub fn set_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Caps the delay between attempts, including the ones asked by `Retry-After`.
+    pub fn set_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// When enabled, each delay is randomly shortened by up to half, so clients failing together
+    /// don't retry together.
+    pub fn set_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// When enabled, waits for as long as asked by MercadoPago on `Retry-After`, either in seconds
+    /// or until an HTTP date.
+    pub fn set_respect_retry_after(mut self, respect_retry_after: bool) -> Self {
+        self.respect_retry_after = respect_retry_after;
+        self
+    }
+
+    pub(crate) fn allows_retries(&self, method: &Method, has_idempotency_key: bool) -> bool {
+        let is_idempotent = matches!(
+            *method,
+            Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS
+        );
+
+        self.max_attempts > 1 && (is_idempotent || has_idempotency_key)
+    }
+
+    pub(crate) fn should_retry(&self, result: &Result<HttpResponse, SDKError>) -> bool {
+        match result {
+            Ok(response) => {
+                response.status == StatusCode::TOO_MANY_REQUESTS
+                    || response.status.is_server_error()
+            }
+            Err(SDKError::NetworkError(e)) => e.is_connect() || e.is_timeout(),
+            Err(_) => false,
+        }
+    }
+
+    /// Delay before attempt `attempt + 1`, after `attempt` failed with `response`.
+    pub(crate) fn delay(&self, attempt: u32, response: Option<&HttpResponse>) -> Duration {
+        let retry_after = response
+            .filter(|_| self.respect_retry_after)
+            .and_then(|r| r.headers.get(RETRY_AFTER))
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| parse_retry_after(v, OffsetDateTime::now_utc()));
+
+        if let Some(retry_after) = retry_after {
+            return retry_after.min(self.max_delay);
+        }
+
+        let exponential = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_delay);
+
+        if self.jitter {
+            exponential.mul_f64(0.5 + fastrand::f64() / 2.0)
+        } else {
+            exponential
+        }
+    }
+}
+
+/// Parses `Retry-After` as either seconds, or an HTTP date waited for since `now`.
+fn parse_retry_after(value: &str, now: OffsetDateTime) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = PrimitiveDateTime::parse(
+        value,
+        format_description!(
+            "[weekday repr:short], [day] [month repr:short] [year] [hour]:[minute]:[second] GMT"
+        ),
+    )
+    .ok()?
+    .assume_utc();
+
+    // dates in the past mean retrying right away
+    Some((date - now).try_into().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::{HeaderMap, HeaderValue};
+
+    use super::*;
+
+    fn response(status: u16, retry_after: Option<&'static str>) -> HttpResponse {
+        let mut headers = HeaderMap::new();
+        if let Some(retry_after) = retry_after {
+            headers.insert(RETRY_AFTER, HeaderValue::from_static(retry_after));
+        }
+
+        HttpResponse {
+            status: StatusCode::from_u16(status).unwrap(),
+            headers,
+            body: String::new(),
+        }
+    }
+
+    #[test]
+    fn t_exponential_delay() {
+        let policy = RetryPolicy::new(5)
+            .set_base_delay(Duration::from_millis(100))
+            .set_max_delay(Duration::from_millis(350))
+            .set_jitter(false);
+
+        assert_eq!(policy.delay(1, None), Duration::from_millis(100));
+        assert_eq!(policy.delay(2, None), Duration::from_millis(200));
+        assert_eq!(policy.delay(3, None), Duration::from_millis(350));
+    }
+
+    #[test]
+    fn t_jittered_delay() {
+        let policy = RetryPolicy::new(3).set_base_delay(Duration::from_millis(100));
+
+        let delay = policy.delay(1, None);
+        assert!(delay >= Duration::from_millis(50) && delay <= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn t_retry_after() {
+        let policy = RetryPolicy::new(3).set_max_delay(Duration::from_secs(5));
+
+        assert_eq!(
+            policy.delay(1, Some(&response(429, Some("2")))),
+            Duration::from_secs(2)
+        );
+        assert_eq!(
+            policy.delay(1, Some(&response(429, Some("60")))),
+            Duration::from_secs(5)
+        );
+    }
+
+    #[test]
+    fn t_retry_after_http_date() {
+        let now = time::macros::datetime!(2015-10-21 07:28:00 UTC);
+
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:30 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:27:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("tomorrow", now), None);
+    }
+
+    #[test]
+    fn t_retryable_failures() {
+        let policy = RetryPolicy::default();
+
+        assert!(policy.should_retry(&Ok(response(502, None))));
+        assert!(policy.should_retry(&Ok(response(429, None))));
+        assert!(!policy.should_retry(&Ok(response(400, None))));
+        assert!(!policy.should_retry(&Err(SDKError::GenericError)));
+
+        assert!(policy.allows_retries(&Method::GET, false));
+        assert!(!policy.allows_retries(&Method::POST, false));
+        assert!(policy.allows_retries(&Method::POST, true));
+        assert!(!RetryPolicy::never().allows_retries(&Method::GET, false));
+    }
+}
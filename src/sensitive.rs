@@ -0,0 +1,103 @@
+//! Secrets kept in memory by the SDK, such as card data and access tokens.
+
+use std::fmt;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use zeroize::Zeroize;
+
+/// An owned string which is wiped from memory when dropped, and never shown by `Debug`.
+///
+/// Card numbers are shown with only their first six and last four digits, as printed on receipts.
+/// Every other value is fully redacted.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SensitiveString(String);
+
+impl SensitiveString {
+    pub fn new<T: Into<String>>(value: T) -> Self {
+        Self(value.into())
+    }
+
+    /// The actual value. Avoid keeping copies of it.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for SensitiveString {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<String> for SensitiveString {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl Drop for SensitiveString {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl fmt::Debug for SensitiveString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let is_card_number =
+            (13..=19).contains(&self.0.len()) && self.0.chars().all(|c| c.is_ascii_digit());
+
+        if is_card_number {
+            write!(
+                f,
+                "\"{}{}{}\"",
+                &self.0[..6],
+                "*".repeat(self.0.len() - 10),
+                &self.0[self.0.len() - 4..]
+            )
+        } else {
+            f.write_str("\"[REDACTED]\"")
+        }
+    }
+}
+
+/// Sent as the plain value, since MercadoPago needs it.
+impl Serialize for SensitiveString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for SensitiveString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_debug_is_redacted() {
+        assert_eq!(
+            format!("{:?}", SensitiveString::from("5031433215406351")),
+            "\"503143******6351\""
+        );
+        assert_eq!(
+            format!("{:?}", SensitiveString::from("123")),
+            "\"[REDACTED]\""
+        );
+        assert_eq!(
+            format!("{:?}", SensitiveString::from("APP_USR-5588812217")),
+            "\"[REDACTED]\""
+        );
+    }
+
+    #[test]
+    fn t_serialization_keeps_value() {
+        assert_eq!(
+            serde_json::to_string(&SensitiveString::from("123")).unwrap(),
+            "\"123\""
+        );
+    }
+}
@@ -0,0 +1,140 @@
+//! `tracing` integration, enabled by the `tracing` feature.
+//!
+//! Every request gets a `mercadopago.request` span carrying its method, path, status, latency,
+//! number of attempts and MercadoPago's `x-request-id`, with one event per attempt. Bodies are only
+//! logged at trace level, with personal and card data replaced by [`REDACTED`].
+
+use std::time::Duration;
+
+use reqwest::Request;
+use serde_json::Value;
+use tracing::field::Empty;
+use tracing::Span;
+
+use crate::errors::SDKError;
+use crate::transport::HttpResponse;
+
+pub(crate) const REDACTED: &str = "[REDACTED]";
+
+/// Keys whose values are never logged, wherever they appear in a body.
+const SENSITIVE_KEYS: &[&str] = &[
+    "address",
+    "card_number",
+    "cardholder",
+    "email",
+    "first_name",
+    "first_six_digits",
+    "identification",
+    "last_four_digits",
+    "last_name",
+    "name",
+    "number",
+    "phone",
+    "receiver_address",
+    "security_code",
+    "surname",
+];
+
+pub(crate) fn request_span(request: &Request) -> Span {
+    let span = tracing::debug_span!(
+        "mercadopago.request",
+        method = %request.method(),
+        path = %request.url().path(),
+        status = Empty,
+        latency_ms = Empty,
+        attempts = Empty,
+        request_id = Empty,
+    );
+
+    if tracing::enabled!(tracing::Level::TRACE) {
+        if let Some(body) = request.body().and_then(|b| b.as_bytes()) {
+            tracing::trace!(
+                parent: &span,
+                body = %redact_body(&String::from_utf8_lossy(body)),
+                "request body"
+            );
+        }
+    }
+
+    span
+}
+
+/// Records an attempt of the request of `span`, which is retried when it failed transiently.
+pub(crate) fn record_attempt(span: &Span, attempt: u32, result: &Result<HttpResponse, SDKError>) {
+    span.record("attempts", attempt);
+
+    match result {
+        Ok(response) => {
+            tracing::debug!(parent: span, attempt, status = response.status.as_u16(), "attempt sent")
+        }
+        Err(e) => tracing::debug!(parent: span, attempt, error = %e, "attempt failed"),
+    }
+}
+
+/// Records the final response of a request, after every attempt.
+pub(crate) fn record_response(span: &Span, response: &HttpResponse, latency: Duration) {
+    span.record("status", response.status.as_u16());
+    span.record("latency_ms", latency.as_millis() as u64);
+    if let Some(request_id) = response
+        .headers
+        .get("x-request-id")
+        .and_then(|v| v.to_str().ok())
+    {
+        span.record("request_id", request_id);
+    }
+
+    tracing::debug!(parent: span, "response received");
+    if tracing::enabled!(tracing::Level::TRACE) {
+        tracing::trace!(parent: span, body = %redact_body(&response.body), "response body");
+    }
+}
+
+/// Replaces every value under a [`SENSITIVE_KEYS`] key. Bodies which are not JSON are not logged.
+pub(crate) fn redact_body(body: &str) -> String {
+    match serde_json::from_str::<Value>(body) {
+        Ok(mut value) => {
+            redact_value(&mut value);
+            value.to_string()
+        }
+        Err(_) => format!("{} ({} bytes, not JSON)", REDACTED, body.len()),
+    }
+}
+
+fn redact_value(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, v) in map.iter_mut() {
+                if SENSITIVE_KEYS.contains(&key.as_str()) && !v.is_null() {
+                    *v = Value::String(REDACTED.to_string());
+                } else {
+                    redact_value(v);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(redact_value),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_redact_body() {
+        let redacted = redact_body(include_str!("../tests/assets/create_payment_response.json"));
+
+        assert!(!redacted.contains("test_user_80507629@testuser.com"));
+        assert!(!redacted.contains("19119119100"));
+        assert!(!redacted.contains("Av das Nacoes Unidas"));
+        assert!(redacted.contains("\"status\":\"approved\""));
+    }
+
+    #[test]
+    fn t_redact_non_json_body() {
+        assert_eq!(
+            redact_body("card_number=4074090000000004"),
+            "[REDACTED] (28 bytes, not JSON)"
+        );
+    }
+}
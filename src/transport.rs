@@ -0,0 +1,45 @@
+//! HTTP transport used by [`crate::SDKRequest::execute`].
+//!
+//! By default, requests are sent through a [`reqwest::Client`], but any type implementing
+//! [`HttpTransport`] can be plugged through
+//! [`MercadoPagoSDKBuilder::set_transport`](crate::MercadoPagoSDKBuilder::set_transport), which is
+//! useful to route requests through a proxy, or to answer them from a local fake in tests.
+
+use std::fmt::Debug;
+
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use reqwest::header::HeaderMap;
+use reqwest::{Client, Request, StatusCode};
+
+use crate::errors::SDKError;
+
+/// A fully read HTTP response.
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: String,
+}
+
+/// Sends an already built [`Request`] and returns its [`HttpResponse`].
+///
+/// Requests reaching the transport already carry the access token, headers and serialized body.
+pub trait HttpTransport: Debug + Send + Sync {
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<HttpResponse, SDKError>>;
+}
+
+impl HttpTransport for Client {
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<HttpResponse, SDKError>> {
+        async move {
+            let response = self.execute(request).await?;
+
+            Ok(HttpResponse {
+                status: response.status(),
+                headers: response.headers().clone(),
+                body: response.text().await?,
+            })
+        }
+        .boxed()
+    }
+}
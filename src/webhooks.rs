@@ -1,27 +1,584 @@
 //! You can setup your webhooks only
+//!
+//! Legacy IPN notifications, sent as `?topic=payment&id=123` to the `notification_url` of a
+//! preference, are parsed by [`IpnNotification::parse`].
+//!
+//! Use [`WebhookVerifier`] to check that a notification was really sent by MercadoPago, with the
+//! secret shown on your application's webhooks page.
+//!
+//! Since notifications are retried and may arrive out of order, use a
+//! [`deduplication::WebhookDeduplicator`] to process each of them once.
+//!
+//! Ready-made receivers are available for `axum` and `actix-web`, behind the features of the same
+//! name.
 
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use reqwest::Url;
 use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use time::OffsetDateTime;
+
+use crate::errors::WebhookError;
+use crate::helpers::string_enum;
+use crate::merchant_orders::responses::MerchantOrderResponse;
+use crate::payments::responses::CreatePaymentResponse;
+
+#[cfg(feature = "actix-web")]
+pub mod actix;
+#[cfg(feature = "axum")]
+pub mod axum;
+pub mod deduplication;
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct WebhookRequest {
-    #[serde(rename = "id")]
-    pub notification_id: i64,
+    /// Sent either as a number or as a string.
+    #[serde(
+        rename = "id",
+        deserialize_with = "serde_aux::field_attributes::deserialize_string_from_number"
+    )]
+    pub notification_id: String,
     /// When true, indicates that it is running in production.
     pub live_mode: bool,
     #[serde(rename = "type")]
-    pub type_field: String,
+    pub type_field: WebhookTopic,
     pub date_created: String,
+    /// Zero when not sent.
+    #[serde(
+        default,
+        deserialize_with = "serde_aux::field_attributes::deserialize_number_from_string"
+    )]
     pub application_id: i64,
     /// User id of which you are receiving this notification.
+    #[serde(deserialize_with = "serde_aux::field_attributes::deserialize_number_from_string")]
     pub user_id: i64,
+    /// Increases on each change of the resource. Zero when not sent.
+    #[serde(default)]
     pub version: i64,
     pub api_version: String,
-    pub action: String,
+    pub action: WebhookAction,
     pub data: Data,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Data {
-    #[serde(rename = "id")]
-    pub payment_id: String,
+    /// ID of the resource this notification is about, such as a payment ID when the topic is
+    /// [`WebhookTopic::Payment`].
+    pub id: String,
+}
+
+string_enum! {
+    /// Kind of resource a notification is about.
+    #[derive(Default)]
+    pub enum WebhookTopic {
+        #[default]
+        Payment = "payment",
+        MerchantOrder = "merchant_order",
+        /// Merchant orders, when subscribed through the `topic_merchant_order_wh` topic.
+        TopicMerchantOrderWh = "topic_merchant_order_wh",
+        SubscriptionPreapproval = "subscription_preapproval",
+        SubscriptionPreapprovalPlan = "subscription_preapproval_plan",
+        SubscriptionAuthorizedPayment = "subscription_authorized_payment",
+        /// Payment intents of Point devices.
+        PointIntegrationWh = "point_integration_wh",
+        TopicChargebacksWh = "topic_chargebacks_wh",
+        TopicClaimsIntegrationWh = "topic_claims_integration_wh",
+        /// Linking and unlinking of sellers to your application.
+        MpConnect = "mp-connect",
+        WalletConnect = "wallet_connect",
+        Delivery = "delivery",
+    }
+}
+
+string_enum! {
+    /// Event that triggered a notification.
+    #[derive(Default)]
+    pub enum WebhookAction {
+        #[default]
+        PaymentCreated = "payment.created",
+        PaymentUpdated = "payment.updated",
+        ApplicationAuthorized = "application.authorized",
+        ApplicationDeauthorized = "application.deauthorized",
+        Created = "created",
+        Updated = "updated",
+    }
+}
+
+/// Resource referenced by a notification, fetched by
+/// [`MercadoPagoSDK::resolve_webhook`](crate::MercadoPagoSDK::resolve_webhook).
+///
+/// Resources not yet modelled by this crate are returned as raw JSON.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WebhookResource {
+    Payment(Box<CreatePaymentResponse>),
+    MerchantOrder(Box<MerchantOrderResponse>),
+    SubscriptionPreapproval(serde_json::Value),
+    SubscriptionAuthorizedPayment(serde_json::Value),
+    PointPaymentIntent(serde_json::Value),
+    Chargeback(serde_json::Value),
+}
+
+/// A legacy IPN (instant payment notification), sent as query parameters to the
+/// `notification_url`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IpnNotification {
+    pub topic: WebhookTopic,
+    /// ID of the resource this notification is about.
+    pub id: String,
+}
+
+impl IpnNotification {
+    /// Parses either a full URL, such as `https://example.com/ipn?topic=payment&id=123`, or only
+    /// its query string, such as `topic=payment&id=123`.
+    ///
+    /// Notifications sent with `type` and `data.id` parameters are also accepted.
+    pub fn parse(url_or_query: &str) -> Result<Self, WebhookError> {
+        let url = Url::parse(url_or_query).or_else(|_| {
+            let query = url_or_query.trim_start_matches('?');
+            Url::parse(&format!("http://localhost/?{}", query))
+        });
+        let url = url.map_err(|e| WebhookError::MalformedNotification(e.to_string()))?;
+
+        let mut topic = None;
+        let mut id = None;
+        for (key, value) in url.query_pairs() {
+            match key.as_ref() {
+                "topic" | "type" => topic = Some(WebhookTopic::from(value.as_ref())),
+                "id" | "data.id" => id = Some(value.into_owned()),
+                _ => {}
+            }
+        }
+
+        match (topic, id) {
+            (Some(topic), Some(id)) if !id.is_empty() => Ok(Self { topic, id }),
+            _ => Err(WebhookError::MalformedNotification(
+                "expected topic and id parameters".to_string(),
+            )),
+        }
+    }
+}
+
+/// A notification received by a webhook receiver, either JSON or IPN.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WebhookEvent {
+    Webhook(WebhookRequest),
+    Ipn(IpnNotification),
+}
+
+impl WebhookEvent {
+    pub fn topic(&self) -> &WebhookTopic {
+        match self {
+            WebhookEvent::Webhook(webhook) => &webhook.type_field,
+            WebhookEvent::Ipn(ipn) => &ipn.topic,
+        }
+    }
+
+    /// ID of the resource this notification is about.
+    pub fn resource_id(&self) -> &str {
+        match self {
+            WebhookEvent::Webhook(webhook) => &webhook.data.id,
+            WebhookEvent::Ipn(ipn) => &ipn.id,
+        }
+    }
+}
+
+/// Verifies the `x-signature` header sent along every webhook notification.
+///
+/// The signature is an HMAC-SHA256 of a manifest built from the `data.id` query parameter, the
+/// `x-request-id` header and the timestamp of the `x-signature` header, keyed by the webhook
+/// secret.
+#[derive(Clone)]
+pub struct WebhookVerifier {
+    secret: String,
+    tolerance: Duration,
+}
+
+impl std::fmt::Debug for WebhookVerifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookVerifier")
+            .field("secret", &"[REDACTED]")
+            .field("tolerance", &self.tolerance)
+            .finish()
+    }
+}
+
+impl WebhookVerifier {
+    /// Creates a verifier accepting notifications signed up to 5 minutes ago.
+    pub fn new<T: ToString>(secret: T) -> Self {
+        Self {
+            secret: secret.to_string(),
+            tolerance: Duration::from_secs(5 * 60),
+        }
+    }
+
+    /// Sets how far from now the signature timestamp can be.
+    pub fn set_tolerance(mut self, tolerance: Duration) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Verifies a notification received now.
+    ///
+    /// `data_id` is the `data.id` query parameter of the notification URL.
+    pub fn verify(
+        &self,
+        x_signature: &str,
+        x_request_id: Option<&str>,
+        data_id: Option<&str>,
+    ) -> Result<(), WebhookError> {
+        self.verify_at(
+            x_signature,
+            x_request_id,
+            data_id,
+            OffsetDateTime::now_utc(),
+        )
+    }
+
+    /// Same as [`WebhookVerifier::verify`], as if the notification was received at `now`.
+    pub fn verify_at(
+        &self,
+        x_signature: &str,
+        x_request_id: Option<&str>,
+        data_id: Option<&str>,
+        now: OffsetDateTime,
+    ) -> Result<(), WebhookError> {
+        let (ts, v1) = parse_signature(x_signature)?;
+
+        // timestamps are documented in seconds, but are sometimes sent in milliseconds
+        let timestamp: i64 = ts
+            .parse()
+            .map_err(|_| WebhookError::MalformedSignature(format!("invalid ts `{}`", ts)))?;
+        let timestamp_secs = if timestamp > 10_000_000_000 {
+            timestamp / 1000
+        } else {
+            timestamp
+        };
+        if now.unix_timestamp().abs_diff(timestamp_secs) > self.tolerance.as_secs() {
+            return Err(WebhookError::ExpiredTimestamp {
+                timestamp: timestamp_secs,
+            });
+        }
+
+        let expected = hex::decode(v1)
+            .map_err(|_| WebhookError::MalformedSignature("v1 is not hexadecimal".to_string()))?;
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.secret.as_bytes())
+            .expect("HMAC accepts keys of any size");
+        mac.update(signature_manifest(ts, x_request_id, data_id).as_bytes());
+        mac.verify_slice(&expected)
+            .map_err(|_| WebhookError::InvalidSignature)
+    }
+}
+
+impl WebhookVerifier {
+    /// Parses a notification as received by an HTTP handler, verifying its signature.
+    ///
+    /// `query` is the query string of the request, without the leading `?`. Every request with an
+    /// `x-signature` header or a body must be correctly signed. Only requests with neither, and
+    /// with `topic` and `id` parameters, are accepted unsigned as IPN notifications, since
+    /// MercadoPago doesn't sign those: always fetch their resource instead of trusting them.
+    ///
+    /// Since only the `data.id` of the URL is signed, notifications whose body refers to another
+    /// resource are rejected.
+    pub fn verify_notification(
+        &self,
+        query: Option<&str>,
+        x_signature: Option<&str>,
+        x_request_id: Option<&str>,
+        body: &[u8],
+    ) -> Result<WebhookEvent, WebhookError> {
+        let query = query.unwrap_or_default();
+        let is_empty = body.iter().all(u8::is_ascii_whitespace);
+
+        let Some(x_signature) = x_signature else {
+            return if !is_empty || query_param(query, "data.id").is_some() {
+                Err(WebhookError::MissingSignature)
+            } else if query_param(query, "topic").is_some() {
+                IpnNotification::parse(query).map(WebhookEvent::Ipn)
+            } else {
+                Err(WebhookError::MalformedNotification(
+                    "expected a signed notification or topic and id parameters".to_string(),
+                ))
+            };
+        };
+
+        let webhook = serde_json::from_slice::<WebhookRequest>(body)
+            .map_err(|e| WebhookError::MalformedNotification(e.to_string()));
+
+        // the signed id is the one of the URL, which is only read from the body when missing
+        let data_id = query_param(query, "data.id")
+            .or_else(|| webhook.as_ref().ok().map(|w| w.data.id.clone()))
+            .ok_or_else(|| {
+                WebhookError::MalformedNotification("expected a data.id parameter".to_string())
+            })?;
+
+        self.verify(x_signature, x_request_id, Some(&data_id))?;
+
+        // the body isn't signed, so its id must be the signed one
+        let webhook = webhook?;
+        if webhook.data.id != data_id {
+            return Err(WebhookError::InvalidSignature);
+        }
+        Ok(WebhookEvent::Webhook(webhook))
+    }
+}
+
+/// First value of `key` in `query`.
+fn query_param(query: &str, key: &str) -> Option<String> {
+    Url::parse(&format!("http://localhost/?{}", query))
+        .ok()?
+        .query_pairs()
+        .find(|(k, _)| k == key)
+        .map(|(_, value)| value.into_owned())
+}
+
+/// Extracts `ts` and `v1` from `ts=...,v1=...`.
+fn parse_signature(x_signature: &str) -> Result<(&str, &str), WebhookError> {
+    let mut ts = None;
+    let mut v1 = None;
+
+    for part in x_signature.split(',') {
+        match part.trim().split_once('=') {
+            Some(("ts", value)) => ts = Some(value.trim()),
+            Some(("v1", value)) => v1 = Some(value.trim()),
+            _ => {}
+        }
+    }
+
+    match (ts, v1) {
+        (Some(ts), Some(v1)) => Ok((ts, v1)),
+        _ => Err(WebhookError::MalformedSignature(
+            "expected ts and v1 values".to_string(),
+        )),
+    }
+}
+
+/// Builds `id:{data_id};request-id:{x_request_id};ts:{ts};`, leaving out absent values.
+fn signature_manifest(ts: &str, x_request_id: Option<&str>, data_id: Option<&str>) -> String {
+    let mut manifest = String::new();
+
+    if let Some(data_id) = data_id {
+        // alphanumeric ids are signed in lowercase
+        manifest.push_str(&format!("id:{};", data_id.to_lowercase()));
+    }
+    if let Some(x_request_id) = x_request_id {
+        manifest.push_str(&format!("request-id:{};", x_request_id));
+    }
+    manifest.push_str(&format!("ts:{};", ts));
+
+    manifest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_ipn_parsing() {
+        assert_eq!(
+            IpnNotification::parse("topic=payment&id=123").unwrap(),
+            IpnNotification {
+                topic: WebhookTopic::Payment,
+                id: "123".to_string(),
+            }
+        );
+        assert_eq!(
+            IpnNotification::parse("https://example.com/ipn?id=4805238154&topic=merchant_order")
+                .unwrap(),
+            IpnNotification {
+                topic: WebhookTopic::MerchantOrder,
+                id: "4805238154".to_string(),
+            }
+        );
+        assert_eq!(
+            IpnNotification::parse("?data.id=123&type=payment").unwrap(),
+            IpnNotification {
+                topic: WebhookTopic::Payment,
+                id: "123".to_string(),
+            }
+        );
+        assert!(matches!(
+            IpnNotification::parse("topic=payment"),
+            Err(WebhookError::MalformedNotification(_))
+        ));
+    }
+
+    #[test]
+    fn t_webhook_deserialization() {
+        let webhook = serde_json::from_slice::<WebhookRequest>(include_bytes!(
+            "../tests/assets/webhook_payment.json"
+        ))
+        .unwrap();
+
+        assert_eq!(webhook.type_field, WebhookTopic::Payment);
+        assert_eq!(webhook.action, WebhookAction::PaymentCreated);
+        assert_eq!(webhook.data.id, "20359978");
+    }
+
+    const SECRET: &str = "your_secret_key_here";
+    const TS: i64 = 1704908010;
+
+    fn sign(manifest: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(SECRET.as_bytes()).unwrap();
+        mac.update(manifest.as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    fn received_at(ts: i64) -> OffsetDateTime {
+        OffsetDateTime::from_unix_timestamp(ts).unwrap()
+    }
+
+    #[test]
+    fn t_valid_signature() {
+        let v1 = sign("id:123456;request-id:bb56a2f1-6aae-46ac-982e-9dcd3581d08e;ts:1704908010;");
+        let x_signature = format!("ts={},v1={}", TS, v1);
+
+        let result = WebhookVerifier::new(SECRET).verify_at(
+            &x_signature,
+            Some("bb56a2f1-6aae-46ac-982e-9dcd3581d08e"),
+            Some("123456"),
+            received_at(TS + 10),
+        );
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn t_absent_values_are_left_out() {
+        let v1 = sign("ts:1704908010;");
+        let x_signature = format!("ts={},v1={}", TS, v1);
+
+        let result =
+            WebhookVerifier::new(SECRET).verify_at(&x_signature, None, None, received_at(TS));
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn t_invalid_signature() {
+        let v1 = sign("id:123456;request-id:abc;ts:1704908010;");
+        let x_signature = format!("ts={},v1={}", TS, v1);
+
+        let result = WebhookVerifier::new(SECRET).verify_at(
+            &x_signature,
+            Some("abc"),
+            Some("654321"),
+            received_at(TS),
+        );
+
+        assert_eq!(result, Err(WebhookError::InvalidSignature));
+    }
+
+    #[test]
+    fn t_expired_timestamp() {
+        let v1 = sign("ts:1704908010;");
+        let x_signature = format!("ts={},v1={}", TS, v1);
+
+        let result = WebhookVerifier::new(SECRET)
+            .set_tolerance(Duration::from_secs(60))
+            .verify_at(&x_signature, None, None, received_at(TS + 61));
+
+        assert_eq!(
+            result,
+            Err(WebhookError::ExpiredTimestamp { timestamp: TS })
+        );
+    }
+
+    #[test]
+    fn t_extreme_timestamps() {
+        let verifier = WebhookVerifier::new(SECRET);
+
+        for ts in [i64::MIN, i64::MAX] {
+            let result =
+                verifier.verify_at(&format!("ts={},v1=00", ts), None, None, received_at(TS));
+
+            assert!(matches!(result, Err(WebhookError::ExpiredTimestamp { .. })));
+        }
+    }
+
+    #[test]
+    fn t_verify_notification() {
+        let verifier = WebhookVerifier::new(SECRET).set_tolerance(Duration::MAX);
+        let body = include_bytes!("../tests/assets/webhook_payment.json");
+
+        let v1 = sign("id:20359978;request-id:abc;ts:1704908010;");
+        let x_signature = format!("ts={},v1={}", TS, v1);
+        let event = verifier
+            .verify_notification(
+                Some("data.id=20359978&type=payment"),
+                Some(&x_signature),
+                Some("abc"),
+                body,
+            )
+            .unwrap();
+        assert_eq!(event.resource_id(), "20359978");
+
+        assert_eq!(
+            verifier.verify_notification(None, None, None, body),
+            Err(WebhookError::MissingSignature)
+        );
+        assert_eq!(
+            verifier.verify_notification(Some("data.id=999&type=payment"), None, None, b""),
+            Err(WebhookError::MissingSignature)
+        );
+        assert_eq!(
+            verifier.verify_notification(
+                Some("data.id=999&type=payment"),
+                Some(&x_signature),
+                Some("abc"),
+                b"{}"
+            ),
+            Err(WebhookError::InvalidSignature)
+        );
+
+        let forged_body = String::from_utf8_lossy(body).replace("20359978", "999");
+        assert_eq!(
+            verifier.verify_notification(
+                Some("data.id=20359978&type=payment"),
+                Some(&x_signature),
+                Some("abc"),
+                forged_body.as_bytes()
+            ),
+            Err(WebhookError::InvalidSignature)
+        );
+
+        let event = verifier
+            .verify_notification(Some("topic=payment&id=123"), None, None, b"")
+            .unwrap();
+        assert_eq!(
+            event,
+            WebhookEvent::Ipn(IpnNotification {
+                topic: WebhookTopic::Payment,
+                id: "123".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn t_documented_notification_shape() {
+        let webhook = serde_json::from_str::<WebhookRequest>(
+            r#"{
+                "id": "123456",
+                "live_mode": true,
+                "type": "payment",
+                "date_created": "2015-03-25T10:04:58.396-04:00",
+                "user_id": 44444,
+                "api_version": "v1",
+                "action": "payment.created",
+                "data": {"id": "999999999"}
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(webhook.notification_id, "123456");
+        assert_eq!(webhook.version, 0);
+    }
+
+    #[test]
+    fn t_malformed_signature() {
+        let result = WebhookVerifier::new(SECRET).verify("v1=abcdef", None, None);
+
+        assert!(matches!(result, Err(WebhookError::MalformedSignature(_))));
+    }
 }
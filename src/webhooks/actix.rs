@@ -0,0 +1,158 @@
+//! `actix-web` webhook receiver, enabled by the `actix-web` feature.
+//!
+//! ```no_run
+//! use actix_web::{App, HttpServer};
+//! use mercadopago_sdk_rust::webhooks::actix::webhook_resource;
+//! use mercadopago_sdk_rust::webhooks::{WebhookEvent, WebhookVerifier};
+//!
+//! # async fn run() -> std::io::Result<()> {
+//! HttpServer::new(|| {
+//!     App::new().service(webhook_resource(
+//!         "/webhooks/mercadopago",
+//!         WebhookVerifier::new("WEBHOOK_SECRET"),
+//!         |event: WebhookEvent| async move {
+//!             println!("{:?} {}", event.topic(), event.resource_id());
+//!         },
+//!     ))
+//! })
+//! .bind(("0.0.0.0", 8080))?
+//! .run()
+//! .await
+//! # }
+//! ```
+
+use std::future::Future;
+use std::rc::Rc;
+
+use ::actix_web::http::StatusCode;
+use ::actix_web::{web, HttpRequest, HttpResponse, Resource, ResponseError};
+
+use crate::errors::WebhookError;
+use crate::webhooks::{WebhookEvent, WebhookVerifier};
+
+impl ResponseError for WebhookError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::from_u16(self.http_status()).unwrap_or(StatusCode::BAD_REQUEST)
+    }
+}
+
+/// Parses a notification received by `request`, rejecting it unless its signature is valid.
+pub fn verify_request(
+    verifier: &WebhookVerifier,
+    request: &HttpRequest,
+    body: &[u8],
+) -> Result<WebhookEvent, WebhookError> {
+    let header = |name: &str| request.headers().get(name).and_then(|v| v.to_str().ok());
+
+    verifier.verify_notification(
+        Some(request.query_string()),
+        header("x-signature"),
+        header("x-request-id"),
+        body,
+    )
+}
+
+/// Creates a resource receiving notifications on `path`.
+///
+/// Valid notifications are answered with `200 OK` right away, while `callback` runs on a new
+/// task. Invalid ones are answered with `400 Bad Request`, or `401 Unauthorized` when their
+/// signature doesn't match.
+pub fn webhook_resource<F, Fut>(path: &str, verifier: WebhookVerifier, callback: F) -> Resource
+where
+    F: Fn(WebhookEvent) -> Fut + 'static,
+    Fut: Future<Output = ()> + 'static,
+{
+    let callback = Rc::new(callback);
+
+    web::resource(path).route(
+        web::post().to(move |request: HttpRequest, body: web::Bytes| {
+            let verifier = verifier.clone();
+            let callback = callback.clone();
+
+            async move {
+                let event = verify_request(&verifier, &request, &body)?;
+                ::actix_web::rt::spawn(callback(event));
+
+                Ok::<_, WebhookError>(HttpResponse::Ok().finish())
+            }
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use ::actix_web::{test, App};
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::webhooks::{IpnNotification, WebhookTopic};
+
+    #[test]
+    fn t_ipn_is_dispatched() {
+        ::actix_web::rt::System::new().block_on(async {
+            let (tx, mut rx) = mpsc::unbounded_channel();
+            let app = test::init_service(App::new().service(webhook_resource(
+                "/webhooks",
+                WebhookVerifier::new("secret"),
+                move |event| {
+                    let tx = tx.clone();
+                    async move { tx.send(event).unwrap() }
+                },
+            )))
+            .await;
+
+            let request = test::TestRequest::post()
+                .uri("/webhooks?topic=merchant_order&id=4805238154")
+                .to_request();
+            let response = test::call_service(&app, request).await;
+
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(
+                rx.recv().await.unwrap(),
+                WebhookEvent::Ipn(IpnNotification {
+                    topic: WebhookTopic::MerchantOrder,
+                    id: "4805238154".to_string(),
+                })
+            );
+        });
+    }
+
+    #[test]
+    fn t_malformed_notification_is_rejected() {
+        ::actix_web::rt::System::new().block_on(async {
+            let app = test::init_service(App::new().service(webhook_resource(
+                "/webhooks",
+                WebhookVerifier::new("secret"),
+                |_| async {},
+            )))
+            .await;
+
+            let request = test::TestRequest::post().uri("/webhooks").to_request();
+            let response = test::call_service(&app, request).await;
+
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        });
+    }
+
+    #[test]
+    fn t_forged_webhook_is_rejected() {
+        ::actix_web::rt::System::new().block_on(async {
+            let app = test::init_service(App::new().service(webhook_resource(
+                "/webhooks",
+                WebhookVerifier::new("secret"),
+                |_| async { panic!("forged notifications must not be dispatched") },
+            )))
+            .await;
+
+            let request = test::TestRequest::post()
+                .uri("/webhooks?data.id=20359978&type=payment&topic=payment&id=20359978")
+                .insert_header(("x-signature", "ts=1704908010,v1=00"))
+                .insert_header(("x-request-id", "abc"))
+                .set_payload("not json")
+                .to_request();
+            let response = test::call_service(&app, request).await;
+
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        });
+    }
+}
@@ -0,0 +1,198 @@
+//! `axum` webhook receiver, enabled by the `axum` feature.
+//!
+//! ```no_run
+//! use mercadopago_sdk_rust::webhooks::axum::webhook_router;
+//! use mercadopago_sdk_rust::webhooks::{WebhookEvent, WebhookVerifier};
+//!
+//! let app: axum::Router = webhook_router(
+//!     "/webhooks/mercadopago",
+//!     WebhookVerifier::new("WEBHOOK_SECRET"),
+//!     |event: WebhookEvent| async move {
+//!         println!("{:?} {}", event.topic(), event.resource_id());
+//!     },
+//! );
+//! ```
+//!
+//! The returned router can be merged into your application, or served on its own.
+
+use std::future::Future;
+use std::sync::Arc;
+
+use ::axum::body::Bytes;
+use ::axum::extract::{FromRef, FromRequest, Request, State};
+use ::axum::http::StatusCode;
+use ::axum::response::{IntoResponse, Response};
+use ::axum::routing::post;
+use ::axum::{async_trait, Router};
+
+use crate::errors::WebhookError;
+use crate::webhooks::{WebhookEvent, WebhookVerifier};
+
+/// Extracts a [`WebhookEvent`] from the request, rejecting it unless its signature is valid.
+///
+/// The [`WebhookVerifier`] is taken from the router state.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifiedWebhook(pub WebhookEvent);
+
+#[async_trait]
+impl<S> FromRequest<S> for VerifiedWebhook
+where
+    S: Send + Sync,
+    WebhookVerifier: FromRef<S>,
+{
+    type Rejection = WebhookError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let verifier = WebhookVerifier::from_ref(state);
+        let query = req.uri().query().map(str::to_string);
+        let headers = req.headers().clone();
+        let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+
+        let body = Bytes::from_request(req, state)
+            .await
+            .map_err(|e| WebhookError::MalformedNotification(e.body_text()))?;
+
+        verifier
+            .verify_notification(
+                query.as_deref(),
+                header("x-signature"),
+                header("x-request-id"),
+                &body,
+            )
+            .map(VerifiedWebhook)
+    }
+}
+
+impl IntoResponse for WebhookError {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.http_status()).unwrap_or(StatusCode::BAD_REQUEST);
+        (status, self.to_string()).into_response()
+    }
+}
+
+struct ReceiverState<F> {
+    verifier: WebhookVerifier,
+    callback: Arc<F>,
+}
+
+impl<F> Clone for ReceiverState<F> {
+    fn clone(&self) -> Self {
+        Self {
+            verifier: self.verifier.clone(),
+            callback: self.callback.clone(),
+        }
+    }
+}
+
+impl<F> FromRef<ReceiverState<F>> for WebhookVerifier {
+    fn from_ref(state: &ReceiverState<F>) -> Self {
+        state.verifier.clone()
+    }
+}
+
+/// Creates a router receiving notifications on `path`.
+///
+/// Valid notifications are answered with `200 OK` right away, while `callback` runs on a new
+/// task. Invalid ones are answered with `400 Bad Request`, or `401 Unauthorized` when their
+/// signature doesn't match.
+pub fn webhook_router<F, Fut>(path: &str, verifier: WebhookVerifier, callback: F) -> Router
+where
+    F: Fn(WebhookEvent) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let state = ReceiverState {
+        verifier,
+        callback: Arc::new(callback),
+    };
+
+    Router::new()
+        .route(path, post(receive::<F, Fut>))
+        .with_state(state)
+}
+
+async fn receive<F, Fut>(
+    State(state): State<ReceiverState<F>>,
+    VerifiedWebhook(event): VerifiedWebhook,
+) -> StatusCode
+where
+    F: Fn(WebhookEvent) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    tokio::spawn((state.callback)(event));
+    StatusCode::OK
+}
+
+#[cfg(test)]
+mod tests {
+    use ::axum::body::Body;
+    use tokio::sync::mpsc;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::webhooks::{IpnNotification, WebhookTopic};
+
+    #[tokio::test]
+    async fn t_ipn_is_dispatched() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let app = webhook_router("/webhooks", WebhookVerifier::new("secret"), move |event| {
+            let tx = tx.clone();
+            async move { tx.send(event).unwrap() }
+        });
+
+        let response = app
+            .oneshot(
+                Request::post("/webhooks?topic=payment&id=123")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            WebhookEvent::Ipn(IpnNotification {
+                topic: WebhookTopic::Payment,
+                id: "123".to_string(),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn t_unsigned_webhook_is_rejected() {
+        let app = webhook_router("/webhooks", WebhookVerifier::new("secret"), |_| async {});
+
+        let response = app
+            .oneshot(
+                Request::post("/webhooks?data.id=20359978&type=payment")
+                    .body(Body::from(
+                        &include_bytes!("../../tests/assets/webhook_payment.json")[..],
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn t_forged_webhook_is_rejected() {
+        let app = webhook_router("/webhooks", WebhookVerifier::new("secret"), |_| async {
+            panic!("forged notifications must not be dispatched")
+        });
+
+        let response = app
+            .oneshot(
+                Request::post("/webhooks?data.id=20359978&type=payment&topic=payment&id=20359978")
+                    .header("x-signature", "ts=1704908010,v1=00")
+                    .header("x-request-id", "abc")
+                    .body(Body::from("not json"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+}
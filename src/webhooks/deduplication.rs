@@ -0,0 +1,179 @@
+//! MercadoPago retries notifications until they are answered, and may deliver them out of order,
+//! such as a `payment.updated` before its `payment.created`.
+//!
+//! A [`WebhookDeduplicator`] remembers the notifications already processed, so each state
+//! transition of a resource is handled once. Since concurrent deliveries may both be processed
+//! before either is marked, handlers should still be idempotent.
+
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+
+use lru::LruCache;
+
+use crate::webhooks::{WebhookRequest, WebhookTopic};
+
+/// Call [`should_process`](Self::should_process) before handling a notification, and
+/// [`mark_processed`](Self::mark_processed) once it was handled successfully, so a notification
+/// whose handling failed is processed again when MercadoPago retries it.
+///
+/// ```
+/// use mercadopago_sdk_rust::webhooks::deduplication::{
+///     InMemoryDeduplicator, WebhookDeduplicator,
+/// };
+/// use mercadopago_sdk_rust::webhooks::WebhookRequest;
+///
+/// fn handle(webhook: &WebhookRequest) -> Result<(), String> {
+///     Ok(())
+/// }
+///
+/// let deduplicator = InMemoryDeduplicator::default();
+/// let webhook = WebhookRequest::default();
+///
+/// if deduplicator.should_process(&webhook) && handle(&webhook).is_ok() {
+///     deduplicator.mark_processed(&webhook);
+/// }
+/// ```
+pub trait WebhookDeduplicator: Send + Sync {
+    /// Whether `webhook` should be processed.
+    ///
+    /// Returns false for notifications already processed, and for versions of a resource older
+    /// than, or equal to, the latest one processed.
+    fn should_process(&self, webhook: &WebhookRequest) -> bool;
+
+    /// Records `webhook` as processed, so it is dropped when delivered again.
+    fn mark_processed(&self, webhook: &WebhookRequest);
+}
+
+/// Keeps the latest notifications and resources seen in memory, forgetting the least recently
+/// used ones when full.
+///
+/// Since memory is not shared, use one instance per process, and a deduplicator backed by a
+/// shared store when running many of them.
+#[derive(Debug)]
+pub struct InMemoryDeduplicator {
+    state: Mutex<DeduplicatorState>,
+}
+
+#[derive(Debug)]
+struct DeduplicatorState {
+    notification_ids: LruCache<String, ()>,
+    /// Latest version seen of each resource.
+    versions: LruCache<(WebhookTopic, String), i64>,
+}
+
+impl InMemoryDeduplicator {
+    /// Creates a deduplicator remembering up to `capacity` notifications and resources.
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            state: Mutex::new(DeduplicatorState {
+                notification_ids: LruCache::new(capacity),
+                versions: LruCache::new(capacity),
+            }),
+        }
+    }
+}
+
+/// Remembers up to 10000 notifications and resources.
+impl Default for InMemoryDeduplicator {
+    fn default() -> Self {
+        Self::new(NonZeroUsize::new(10_000).unwrap())
+    }
+}
+
+impl WebhookDeduplicator for InMemoryDeduplicator {
+    fn should_process(&self, webhook: &WebhookRequest) -> bool {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        if state.notification_ids.contains(&webhook.notification_id) {
+            return false;
+        }
+
+        // notifications without a version can only be told apart by their ID
+        let resource = (webhook.type_field.clone(), webhook.data.id.clone());
+        match state.versions.peek(&resource) {
+            Some(latest_version) => webhook.version == 0 || webhook.version > *latest_version,
+            None => true,
+        }
+    }
+
+    fn mark_processed(&self, webhook: &WebhookRequest) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        state
+            .notification_ids
+            .put(webhook.notification_id.clone(), ());
+        if webhook.version > 0 {
+            let resource = (webhook.type_field.clone(), webhook.data.id.clone());
+            let latest_version = state.versions.peek(&resource).copied().unwrap_or_default();
+            state
+                .versions
+                .put(resource, webhook.version.max(latest_version));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::webhooks::{Data, WebhookAction};
+
+    fn webhook(notification_id: i64, payment_id: &str, version: i64) -> WebhookRequest {
+        WebhookRequest {
+            notification_id: notification_id.to_string(),
+            version,
+            action: WebhookAction::PaymentUpdated,
+            data: Data {
+                id: payment_id.to_string(),
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Processes `webhook` like a receiver would, when it should.
+    fn process(deduplicator: &InMemoryDeduplicator, webhook: &WebhookRequest) -> bool {
+        let should_process = deduplicator.should_process(webhook);
+        if should_process {
+            deduplicator.mark_processed(webhook);
+        }
+        should_process
+    }
+
+    #[test]
+    fn t_drops_retried_notifications() {
+        let deduplicator = InMemoryDeduplicator::default();
+
+        assert!(process(&deduplicator, &webhook(1, "123", 1)));
+        assert!(!process(&deduplicator, &webhook(1, "123", 1)));
+    }
+
+    #[test]
+    fn t_drops_stale_versions() {
+        let deduplicator = InMemoryDeduplicator::default();
+
+        assert!(process(&deduplicator, &webhook(2, "123", 2)));
+        assert!(!process(&deduplicator, &webhook(1, "123", 1)));
+        assert!(process(&deduplicator, &webhook(3, "123", 3)));
+        assert!(process(&deduplicator, &webhook(4, "456", 1)));
+    }
+
+    #[test]
+    fn t_redelivers_after_failed_handler() {
+        let deduplicator = InMemoryDeduplicator::default();
+        let notification = webhook(1, "123", 1);
+
+        // the handler failed, so it was never marked as processed
+        assert!(deduplicator.should_process(&notification));
+
+        assert!(process(&deduplicator, &notification));
+        assert!(!deduplicator.should_process(&notification));
+    }
+
+    #[test]
+    fn t_forgets_least_recently_used() {
+        let deduplicator = InMemoryDeduplicator::new(NonZeroUsize::new(1).unwrap());
+
+        assert!(process(&deduplicator, &webhook(1, "123", 1)));
+        assert!(process(&deduplicator, &webhook(2, "456", 1)));
+        assert!(process(&deduplicator, &webhook(1, "123", 1)));
+    }
+}
@@ -1,7 +1,28 @@
+use std::time::Duration;
+
 use mercadopago_sdk_rust::card_tokens::requests::CardTokenOptions;
 use mercadopago_sdk_rust::common_types::{CheckoutProPayer, Item, PersonalIdentification};
-use mercadopago_sdk_rust::payments::requests::DocumentType;
+use mercadopago_sdk_rust::customers::requests::{
+    CreateCustomerPayload, CustomerSearchOptions, UpdateCustomerPayload,
+};
+use mercadopago_sdk_rust::errors::{
+    CreditCardError, ErrorCauseCode, SDKError, ValidationError, WebhookError,
+};
+use mercadopago_sdk_rust::payment_methods::responses::PaymentMethodStatus;
+use mercadopago_sdk_rust::payments::pix::PixPayment;
+use mercadopago_sdk_rust::payments::requests::{
+    CreatePaymentPayload, DocumentType, PaymentDateField, PaymentMethodId, PaymentSearchOptions,
+    PaymentSortField, SortCriteria, UpdatePaymentPayload,
+};
+use mercadopago_sdk_rust::payments::responses::{PaymentStatus, StatusDetail};
 use mercadopago_sdk_rust::preferences::requests::CheckoutProPreferences;
+use mercadopago_sdk_rust::retry::RetryPolicy;
+use mercadopago_sdk_rust::webhooks::{
+    Data, IpnNotification, WebhookAction, WebhookRequest, WebhookResource, WebhookTopic,
+};
+use mercadopago_sdk_rust::{MercadoPagoSDKBuilder, IDEMPOTENCY_KEY_HEADER};
+use reqwest::Method;
+use time::macros::datetime;
 
 mod common;
 
@@ -17,7 +38,7 @@ async fn create_preference() {
         .set_payer(CheckoutProPayer::minimal_payer(
             "fulano@beltrano.com.br".to_string(),
             DocumentType::CPF,
-            41810524485,
+            "41810524431",
         ));
 
     sdk.create_preferences_checkout_pro(preferences)
@@ -31,11 +52,11 @@ async fn create_preference() {
 async fn tokenize_card() {
     let sdk = common::create_sdk();
 
-    let card_owner_pi = PersonalIdentification::new(DocumentType::CPF, 12345678909);
+    let card_owner_pi = PersonalIdentification::new(DocumentType::CPF, "12345678909").unwrap();
     let token_opts = CardTokenOptions::new(
         "5031433215406351",
         "11",
-        "2025",
+        "2030",
         "123",
         "APRO".to_string(),
         card_owner_pi,
@@ -47,3 +68,1102 @@ async fn tokenize_card() {
         .await
         .unwrap();
 }
+
+#[tokio::test]
+async fn create_preference_through_custom_transport() {
+    let stub = common::StubTransport::new().route(
+        Method::POST,
+        "/checkout/preferences",
+        201,
+        include_str!("assets/checkout_preferences_response.json"),
+    );
+    let sdk = common::create_stub_sdk(&stub);
+
+    let sample_item =
+        Item::minimal_item("Sample item".to_string(), "".to_string(), 15.00, 1).unwrap();
+    let preferences = CheckoutProPreferences::new()
+        .set_items(vec![sample_item])
+        .set_payer(CheckoutProPayer::minimal_payer(
+            "fulano@beltrano.com.br".to_string(),
+            DocumentType::CPF,
+            "41810524431",
+        ));
+
+    sdk.create_preferences_checkout_pro(preferences)
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+
+    let requests = stub.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/checkout/preferences");
+    assert_eq!(
+        requests[0].headers["authorization"],
+        "Bearer TEST_ACCESS_TOKEN"
+    );
+}
+
+#[tokio::test]
+async fn create_preference_without_payer() {
+    let stub = common::StubTransport::new().route(
+        Method::POST,
+        "/checkout/preferences",
+        201,
+        include_str!("assets/checkout_preferences_response.json"),
+    );
+    let sdk = common::create_stub_sdk(&stub);
+
+    let sample_item =
+        Item::minimal_item("Sample item".to_string(), "".to_string(), 15.00, 1).unwrap();
+    let preferences = CheckoutProPreferences::new().set_items(vec![sample_item]);
+
+    sdk.create_preferences_checkout_pro(preferences)
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+
+    assert_eq!(stub.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn tokenize_card_through_custom_transport() {
+    let stub = common::StubTransport::new().route(
+        Method::POST,
+        "/v1/card_tokens",
+        201,
+        include_str!("assets/card_tokens_create_response.json"),
+    );
+    let sdk = common::create_stub_sdk(&stub);
+
+    let card_owner_pi = PersonalIdentification::new(DocumentType::CPF, "12345678909").unwrap();
+    let token_opts = CardTokenOptions::new(
+        "5031433215406351",
+        "11",
+        "2030",
+        "123",
+        "APRO".to_string(),
+        card_owner_pi,
+    );
+
+    sdk.create_card_token(token_opts)
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+
+    let requests = stub.requests();
+    assert_eq!(requests[0].path, "/v1/card_tokens?public_key=");
+    assert_eq!(
+        requests[0].body.as_ref().unwrap()["card_number"],
+        "5031433215406351"
+    );
+}
+
+#[tokio::test]
+async fn api_errors_are_typed() {
+    let stub = common::StubTransport::new().route(
+        Method::POST,
+        "/v1/card_tokens",
+        400,
+        r#"{
+            "message": "Invalid card_number_validation",
+            "error": "bad_request",
+            "status": 400,
+            "cause": [{"code": "3034", "description": "Invalid card_number_validation"}]
+        }"#,
+    );
+    let sdk = common::create_stub_sdk(&stub);
+
+    let card_owner_pi = PersonalIdentification::new(DocumentType::CPF, "12345678909").unwrap();
+    // a card passing offline validation, rejected by MercadoPago
+    let token_opts = CardTokenOptions::new(
+        "5031433215406351",
+        "11",
+        "2030",
+        "123",
+        "APRO".to_string(),
+        card_owner_pi,
+    );
+
+    let err = sdk
+        .create_card_token(token_opts)
+        .unwrap()
+        .execute()
+        .await
+        .unwrap_err();
+
+    match err {
+        SDKError::Api {
+            status,
+            error,
+            causes,
+            ..
+        } => {
+            assert_eq!(status, 400);
+            assert_eq!(error, "bad_request");
+            assert_eq!(causes.len(), 1);
+            assert_eq!(causes[0].code, ErrorCauseCode::InvalidCardNumberValidation);
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+#[test]
+fn invalid_card_is_rejected_before_tokenizing() {
+    let stub = common::StubTransport::new();
+    let sdk = common::create_stub_sdk(&stub);
+
+    let card_owner_pi = PersonalIdentification::new(DocumentType::CPF, "12345678909").unwrap();
+    let token_opts = CardTokenOptions::new(
+        "5031433215406352",
+        "11",
+        "2030",
+        "123",
+        "APRO".to_string(),
+        card_owner_pi,
+    );
+
+    assert!(matches!(
+        sdk.create_card_token(token_opts),
+        Err(SDKError::ValidationError(ValidationError::CreditCard(
+            CreditCardError::InvalidChecksum
+        )))
+    ));
+    assert!(stub.requests().is_empty());
+}
+
+#[tokio::test]
+async fn deserialization_errors_keep_path_and_body() {
+    let stub =
+        common::StubTransport::new().route(Method::POST, "/v1/card_tokens", 201, r#"{"id": 123}"#);
+    let sdk = common::create_stub_sdk(&stub);
+
+    let card_owner_pi = PersonalIdentification::new(DocumentType::CPF, "12345678909").unwrap();
+    let token_opts = CardTokenOptions::new(
+        "5031433215406351",
+        "11",
+        "2030",
+        "123",
+        "APRO".to_string(),
+        card_owner_pi,
+    );
+
+    let err = sdk
+        .create_card_token(token_opts)
+        .unwrap()
+        .execute()
+        .await
+        .unwrap_err();
+
+    match err {
+        SDKError::DeserializationError { path, body, .. } => {
+            assert_eq!(path, "id");
+            assert_eq!(body, r#"{"id": 123}"#);
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn get_payment() {
+    let stub = common::StubTransport::new().route(
+        Method::GET,
+        "/v1/payments/20359978",
+        200,
+        include_str!("assets/create_payment_response.json"),
+    );
+    let sdk = common::create_stub_sdk(&stub);
+
+    let payment = sdk.get_payment(20359978).unwrap().execute().await.unwrap();
+
+    assert_eq!(payment.id, 20359978);
+    assert_eq!(payment.external_reference.as_deref(), Some("MP0001"));
+}
+
+#[tokio::test]
+async fn search_payments() {
+    let search_response = format!(
+        r#"{{"paging": {{"total": 1, "limit": 10, "offset": 20}}, "results": [{}]}}"#,
+        include_str!("assets/create_payment_response.json")
+    );
+    let stub = common::StubTransport::new().route(
+        Method::GET,
+        "/v1/payments/search",
+        200,
+        &search_response,
+    );
+    let sdk = common::create_stub_sdk(&stub);
+
+    let filters = PaymentSearchOptions::new()
+        .set_status(PaymentStatus::Approved)
+        .set_external_reference("MP0001")
+        .set_date_range(
+            PaymentDateField::DateCreated,
+            datetime!(2019-07-01 00:00 UTC),
+            datetime!(2019-08-01 00:00 UTC),
+        )
+        .set_sort(PaymentSortField::DateCreated, SortCriteria::Desc)
+        .set_pagination(10, 20);
+
+    let page = sdk
+        .search_payments(filters)
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+
+    assert_eq!(page.paging.total, 1);
+    assert_eq!(page.results[0].id, 20359978);
+    assert_eq!(
+        stub.requests()[0].path,
+        "/v1/payments/search?status=approved&external_reference=MP0001&sort=date_created&\
+         criteria=desc&range=date_created&begin_date=2019-07-01T00%3A00%3A00Z&\
+         end_date=2019-08-01T00%3A00%3A00Z&limit=10&offset=20"
+    );
+}
+
+#[tokio::test]
+async fn update_payment() {
+    let stub = common::StubTransport::new().route(
+        Method::PUT,
+        "/v1/payments/20359978",
+        200,
+        include_str!("assets/create_payment_response.json"),
+    );
+    let sdk = common::create_stub_sdk(&stub);
+
+    let patch = UpdatePaymentPayload {
+        external_reference: Some("MP0002".to_string()),
+        ..Default::default()
+    };
+
+    sdk.update_payment(20359978, patch)
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        stub.requests()[0].body,
+        Some(serde_json::json!({"external_reference": "MP0002"}))
+    );
+}
+
+#[tokio::test]
+async fn create_payment() {
+    let stub = common::StubTransport::new().route(
+        Method::POST,
+        "/v1/payments",
+        201,
+        include_str!("assets/create_payment_response.json"),
+    );
+    let sdk = common::create_stub_sdk(&stub);
+
+    let payload = serde_json::from_str::<CreatePaymentPayload>(include_str!(
+        "assets/create_payment_request.json"
+    ))
+    .unwrap();
+
+    let payment = sdk
+        .create_payment(payload)
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+
+    assert_eq!(payment.id, 20359978);
+    assert_eq!(payment.status, PaymentStatus::Approved);
+    assert_eq!(payment.status_detail, StatusDetail::Accredited);
+
+    let request = &stub.requests()[0];
+    assert_eq!(request.path, "/v1/payments");
+    assert!(request.headers.contains_key(IDEMPOTENCY_KEY_HEADER));
+
+    let body = request.body.as_ref().unwrap();
+    assert_eq!(body["payment_method_id"], "visa");
+    assert_eq!(body["transaction_amount"], 58.8);
+    assert_eq!(body["external_reference"], "MP0001");
+    assert!(body.get("token").is_none());
+}
+
+#[tokio::test]
+async fn explicit_idempotency_key() {
+    let stub = common::StubTransport::new().route(
+        Method::POST,
+        "/v1/payments",
+        201,
+        include_str!("assets/create_payment_response.json"),
+    );
+    let sdk = common::create_stub_sdk(&stub);
+
+    let payload = serde_json::from_str::<CreatePaymentPayload>(include_str!(
+        "assets/create_payment_request.json"
+    ))
+    .unwrap();
+
+    sdk.create_payment(payload)
+        .unwrap()
+        .idempotency_key("order-1234")
+        .execute()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        stub.requests()[0].headers[IDEMPOTENCY_KEY_HEADER],
+        "order-1234"
+    );
+}
+
+#[tokio::test]
+async fn automatic_idempotency_keys() {
+    let stub = common::StubTransport::new()
+        .route(
+            Method::PUT,
+            "/v1/payments/20359978",
+            200,
+            include_str!("assets/create_payment_response.json"),
+        )
+        .route(
+            Method::GET,
+            "/v1/payments/20359978",
+            200,
+            include_str!("assets/create_payment_response.json"),
+        );
+    let sdk = MercadoPagoSDKBuilder::new("TEST_ACCESS_TOKEN")
+        .set_base_url(common::STUB_BASE_URL)
+        .set_transport(stub.clone())
+        .set_automatic_idempotency_keys(true)
+        .build();
+
+    sdk.update_payment(20359978, UpdatePaymentPayload::default())
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+    sdk.get_payment(20359978).unwrap().execute().await.unwrap();
+
+    let requests = stub.requests();
+    assert!(requests[0].headers.contains_key(IDEMPOTENCY_KEY_HEADER));
+    assert!(!requests[1].headers.contains_key(IDEMPOTENCY_KEY_HEADER));
+}
+
+#[tokio::test]
+async fn retries_transient_failures() {
+    let stub = common::StubTransport::new()
+        .route(Method::GET, "/v1/payments/20359978", 502, "Bad Gateway")
+        .route(
+            Method::GET,
+            "/v1/payments/20359978",
+            429,
+            r#"{"message":"too many requests","error":"too_many_requests","status":429}"#,
+        )
+        .route(
+            Method::GET,
+            "/v1/payments/20359978",
+            200,
+            include_str!("assets/create_payment_response.json"),
+        );
+    let sdk = MercadoPagoSDKBuilder::new("TEST_ACCESS_TOKEN")
+        .set_base_url(common::STUB_BASE_URL)
+        .set_transport(stub.clone())
+        .set_retry_policy(RetryPolicy::new(3).set_base_delay(Duration::from_millis(1)))
+        .build();
+
+    let payment = sdk.get_payment(20359978).unwrap().execute().await.unwrap();
+
+    assert_eq!(payment.id, 20359978);
+    assert_eq!(stub.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn does_not_retry_requests_without_idempotency_key() {
+    let stub = common::StubTransport::new().route(
+        Method::POST,
+        "/checkout/preferences",
+        503,
+        "Service Unavailable",
+    );
+    let sdk = MercadoPagoSDKBuilder::new("TEST_ACCESS_TOKEN")
+        .set_base_url(common::STUB_BASE_URL)
+        .set_transport(stub.clone())
+        .set_retry_policy(RetryPolicy::new(3).set_base_delay(Duration::from_millis(1)))
+        .build();
+
+    let sample_item =
+        Item::minimal_item("Sample item".to_string(), "".to_string(), 15.00, 1).unwrap();
+    let preferences = CheckoutProPreferences::new()
+        .set_items(vec![sample_item])
+        .set_payer(CheckoutProPayer::minimal_payer(
+            "fulano@beltrano.com.br".to_string(),
+            DocumentType::CPF,
+            "41810524431",
+        ));
+
+    let err = sdk
+        .create_preferences_checkout_pro(preferences)
+        .unwrap()
+        .execute()
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, SDKError::Api { status: 503, .. }));
+    assert_eq!(stub.requests().len(), 1);
+}
+
+#[test]
+fn preference_with_invalid_payer_identification_is_rejected() {
+    let sdk = common::create_stub_sdk(&common::StubTransport::new());
+
+    let sample_item =
+        Item::minimal_item("Sample item".to_string(), "".to_string(), 15.00, 1).unwrap();
+    let preferences = CheckoutProPreferences::new()
+        .set_items(vec![sample_item])
+        .set_payer(CheckoutProPayer::minimal_payer(
+            "fulano@beltrano.com.br".to_string(),
+            DocumentType::CPF,
+            "41810524485",
+        ));
+
+    assert!(matches!(
+        sdk.create_preferences_checkout_pro(preferences),
+        Err(SDKError::ValidationError(ValidationError::PayerError(_)))
+    ));
+}
+
+#[tokio::test]
+async fn retries_reuse_idempotency_key() {
+    let stub = common::StubTransport::new()
+        .route(Method::POST, "/v1/payments", 500, "Internal Server Error")
+        .route(
+            Method::POST,
+            "/v1/payments",
+            201,
+            include_str!("assets/create_payment_response.json"),
+        );
+    let sdk = MercadoPagoSDKBuilder::new("TEST_ACCESS_TOKEN")
+        .set_base_url(common::STUB_BASE_URL)
+        .set_transport(stub.clone())
+        .set_retry_policy(RetryPolicy::new(3).set_base_delay(Duration::from_millis(1)))
+        .build();
+
+    let payload = serde_json::from_str::<CreatePaymentPayload>(include_str!(
+        "assets/create_payment_request.json"
+    ))
+    .unwrap();
+
+    sdk.create_payment(payload)
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+
+    let requests = stub.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(
+        requests[0].headers[IDEMPOTENCY_KEY_HEADER],
+        requests[1].headers[IDEMPOTENCY_KEY_HEADER]
+    );
+}
+
+#[tokio::test]
+async fn create_refund() {
+    let stub = common::StubTransport::new().route(
+        Method::POST,
+        "/v1/payments/18552260055/refunds",
+        201,
+        include_str!("assets/create_refund_response.json"),
+    );
+    let sdk = common::create_stub_sdk(&stub);
+
+    let refund = sdk
+        .create_refund(18552260055, Some(10.0))
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+    sdk.create_refund(18552260055, None)
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+
+    assert_eq!(refund.id, 1009042015);
+    assert_eq!(refund.source.id, "130379930");
+
+    let requests = stub.requests();
+    assert_eq!(requests[0].body, Some(serde_json::json!({"amount": 10.0})));
+    assert_eq!(requests[1].body, Some(serde_json::json!({})));
+    assert_ne!(
+        requests[0].headers[IDEMPOTENCY_KEY_HEADER],
+        requests[1].headers[IDEMPOTENCY_KEY_HEADER]
+    );
+}
+
+#[tokio::test]
+async fn list_and_get_refunds() {
+    let refund = include_str!("assets/create_refund_response.json");
+    let stub = common::StubTransport::new()
+        .route(
+            Method::GET,
+            "/v1/payments/18552260055/refunds",
+            200,
+            &format!("[{}]", refund),
+        )
+        .route(
+            Method::GET,
+            "/v1/payments/18552260055/refunds/1009042015",
+            200,
+            refund,
+        );
+    let sdk = common::create_stub_sdk(&stub);
+
+    let refunds = sdk
+        .list_refunds(18552260055)
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+    let refund = sdk
+        .get_refund(18552260055, 1009042015)
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+
+    assert_eq!(refunds.len(), 1);
+    assert_eq!(refunds[0], refund);
+}
+
+#[tokio::test]
+async fn authorize_then_capture_payment() {
+    let stub = common::StubTransport::new()
+        .route(
+            Method::POST,
+            "/v1/payments",
+            201,
+            include_str!("assets/create_payment_response.json"),
+        )
+        .route(
+            Method::PUT,
+            "/v1/payments/20359978",
+            200,
+            include_str!("assets/create_payment_response.json"),
+        );
+    let sdk = common::create_stub_sdk(&stub);
+
+    let mut payload = serde_json::from_str::<CreatePaymentPayload>(include_str!(
+        "assets/create_payment_request.json"
+    ))
+    .unwrap();
+    payload.capture = Some(false);
+
+    let payment = sdk
+        .create_payment(payload)
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+    sdk.capture_payment(payment.id, Some(50.0))
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+
+    let requests = stub.requests();
+    assert_eq!(requests[0].body.as_ref().unwrap()["capture"], false);
+    assert_eq!(
+        requests[1].body,
+        Some(serde_json::json!({"capture": true, "transaction_amount": 50.0}))
+    );
+    assert!(requests[1].headers.contains_key(IDEMPOTENCY_KEY_HEADER));
+}
+
+#[tokio::test]
+async fn cancel_payment() {
+    let stub = common::StubTransport::new().route(
+        Method::PUT,
+        "/v1/payments/20359978",
+        200,
+        include_str!("assets/create_payment_response.json"),
+    );
+    let sdk = common::create_stub_sdk(&stub);
+
+    sdk.cancel_payment(20359978)
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        stub.requests()[0].body,
+        Some(serde_json::json!({"status": "cancelled"}))
+    );
+}
+
+#[tokio::test]
+async fn resolve_payment_webhook() {
+    let stub = common::StubTransport::new().route(
+        Method::GET,
+        "/v1/payments/20359978",
+        200,
+        include_str!("assets/create_payment_response.json"),
+    );
+    let sdk = common::create_stub_sdk(&stub);
+
+    let webhook =
+        serde_json::from_str::<WebhookRequest>(include_str!("assets/webhook_payment.json"))
+            .unwrap();
+
+    match sdk.resolve_webhook(&webhook).await.unwrap() {
+        WebhookResource::Payment(payment) => assert_eq!(payment.id, 20359978),
+        other => panic!("unexpected resource: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn resolve_merchant_order_webhook() {
+    let stub = common::StubTransport::new().route(
+        Method::GET,
+        "/merchant_orders/4805238154",
+        200,
+        include_str!("assets/merchant_order_response.json"),
+    );
+    let sdk = common::create_stub_sdk(&stub);
+
+    let webhook = WebhookRequest {
+        type_field: WebhookTopic::TopicMerchantOrderWh,
+        action: WebhookAction::Updated,
+        data: Data {
+            id: "4805238154".to_string(),
+        },
+        ..Default::default()
+    };
+
+    match sdk.resolve_webhook(&webhook).await.unwrap() {
+        WebhookResource::MerchantOrder(order) => {
+            assert_eq!(order.payments[0].status, PaymentStatus::Approved)
+        }
+        other => panic!("unexpected resource: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn resolve_unsupported_webhook() {
+    let sdk = common::create_stub_sdk(&common::StubTransport::new());
+
+    let webhook = WebhookRequest {
+        type_field: WebhookTopic::Unknown("new_topic".to_string()),
+        ..Default::default()
+    };
+
+    let err = sdk.resolve_webhook(&webhook).await.unwrap_err();
+    assert!(matches!(
+        err,
+        SDKError::WebhookError(WebhookError::UnsupportedTopic(_))
+    ));
+}
+
+#[tokio::test]
+async fn resolve_ipn_notification() {
+    let stub = common::StubTransport::new().route(
+        Method::GET,
+        "/merchant_orders/4805238154",
+        200,
+        include_str!("assets/merchant_order_response.json"),
+    );
+    let sdk = common::create_stub_sdk(&stub);
+
+    let notification = IpnNotification::parse(
+        "https://www.suaurl.com/notificacoes/?topic=merchant_order&id=4805238154",
+    )
+    .unwrap();
+
+    match sdk.resolve_ipn(&notification).await.unwrap() {
+        WebhookResource::MerchantOrder(order) => assert_eq!(order.id, 4805238154),
+        other => panic!("unexpected resource: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn customers_lifecycle() {
+    let customer = include_str!("assets/customer_response.json");
+    let customer_path = "/v1/customers/1162600213-pgQxUNnEJnq1xF";
+    let stub = common::StubTransport::new()
+        .route(Method::POST, "/v1/customers", 201, customer)
+        .route(Method::GET, customer_path, 200, customer)
+        .route(Method::PUT, customer_path, 200, customer)
+        .route(Method::DELETE, customer_path, 200, customer)
+        .route(
+            Method::GET,
+            "/v1/customers/search",
+            200,
+            &format!(
+                r#"{{"paging": {{"total": 1, "limit": 10, "offset": 0}}, "results": [{}]}}"#,
+                customer
+            ),
+        );
+    let sdk = common::create_stub_sdk(&stub);
+
+    let payload = CreateCustomerPayload {
+        first_name: Some("Jhon".to_string()),
+        identification: Some(
+            PersonalIdentification::new(DocumentType::CPF, "12345678909").unwrap(),
+        ),
+        ..CreateCustomerPayload::new("jhon@doe.com")
+    };
+    let customer = sdk
+        .create_customer(payload)
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+
+    sdk.get_customer(&customer.id)
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+    let found = sdk
+        .search_customers(CustomerSearchOptions::new().set_email("jhon@doe.com"))
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+    sdk.update_customer(
+        &customer.id,
+        UpdateCustomerPayload {
+            description: Some("VIP".to_string()),
+            ..Default::default()
+        },
+    )
+    .unwrap()
+    .execute()
+    .await
+    .unwrap();
+    sdk.delete_customer(&customer.id)
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+
+    assert_eq!(found.results[0].id, customer.id);
+
+    let requests = stub.requests();
+    assert_eq!(
+        requests[0].body,
+        Some(serde_json::json!({
+            "email": "jhon@doe.com",
+            "first_name": "Jhon",
+            "identification": {"type": "CPF", "number": "12345678909"}
+        }))
+    );
+    assert_eq!(
+        requests[2].path,
+        "/v1/customers/search?email=jhon%40doe.com"
+    );
+    assert_eq!(
+        requests[3].body,
+        Some(serde_json::json!({"description": "VIP"}))
+    );
+    assert_eq!(requests[4].method, Method::DELETE);
+}
+
+#[tokio::test]
+async fn customer_cards_lifecycle() {
+    let card = include_str!("assets/customer_card_response.json");
+    let cards_path = "/v1/customers/1162600213-pgQxUNnEJnq1xF/cards";
+    let card_path = "/v1/customers/1162600213-pgQxUNnEJnq1xF/cards/9016637386";
+    let stub = common::StubTransport::new()
+        .route(Method::POST, cards_path, 201, card)
+        .route(Method::GET, cards_path, 200, &format!("[{}]", card))
+        .route(Method::GET, card_path, 200, card)
+        .route(Method::DELETE, card_path, 200, card)
+        .route(
+            Method::POST,
+            "/v1/card_tokens",
+            201,
+            include_str!("assets/card_tokens_create_response.json"),
+        );
+    let sdk = common::create_stub_sdk(&stub);
+    let customer_id = "1162600213-pgQxUNnEJnq1xF";
+
+    let saved = sdk
+        .save_customer_card(customer_id, "ff8080814c11e237014c1ff593b57b4d")
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+    let cards = sdk
+        .list_customer_cards(customer_id)
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+    sdk.get_customer_card(customer_id, &saved.id)
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+    sdk.create_card_token(CardTokenOptions::saved_card(&saved.id, "123"))
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+    sdk.delete_customer_card(customer_id, &saved.id)
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+
+    assert_eq!(cards.len(), 1);
+    assert_eq!(cards[0].card.cardholder.name, "APRO");
+
+    let requests = stub.requests();
+    assert_eq!(
+        requests[0].body,
+        Some(serde_json::json!({"token": "ff8080814c11e237014c1ff593b57b4d"}))
+    );
+    assert_eq!(
+        requests[3].body,
+        Some(serde_json::json!({"card_id": "9016637386", "security_code": "123"}))
+    );
+    assert_eq!(requests[4].method, Method::DELETE);
+}
+
+#[tokio::test]
+async fn get_payment_methods() {
+    let stub = common::StubTransport::new().route(
+        Method::GET,
+        "/v1/payment_methods",
+        200,
+        include_str!("assets/payment_methods_response.json"),
+    );
+    let sdk = common::create_stub_sdk(&stub);
+
+    let methods = sdk.get_payment_methods().unwrap().execute().await.unwrap();
+
+    assert_eq!(methods.len(), 10);
+    assert!(methods
+        .iter()
+        .any(|m| m.id == PaymentMethodId::Pix && m.status == PaymentMethodStatus::Active));
+}
+
+#[tokio::test]
+async fn get_installments() {
+    let stub = common::StubTransport::new().route(
+        Method::GET,
+        "/v1/payment_methods/installments",
+        200,
+        include_str!("assets/installments_response.json"),
+    );
+    let sdk = common::create_stub_sdk(&stub);
+
+    let options = sdk
+        .get_installments(100.0, Some("450995"), Some(PaymentMethodId::Visa), None)
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+
+    let plan = options[0].payer_cost(3).unwrap();
+    assert_eq!(plan.installment_amount, 36.15);
+    assert_eq!(
+        plan.recommended_message,
+        "3 parcelas de R$ 36,15 (R$ 108,45)"
+    );
+    assert_eq!(
+        stub.requests()[0].path,
+        "/v1/payment_methods/installments?amount=100.0&bin=450995&payment_method_id=visa"
+    );
+}
+
+#[tokio::test]
+async fn get_identification_types() {
+    let stub = common::StubTransport::new().route(
+        Method::GET,
+        "/v1/identification_types",
+        200,
+        include_str!("assets/identification_types_response.json"),
+    );
+    let sdk = common::create_stub_sdk(&stub);
+
+    let types = sdk
+        .get_identification_types()
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+
+    assert_eq!(types.len(), 3);
+    assert_eq!(types[1].id, DocumentType::CNPJ);
+}
+
+#[test]
+fn invalid_identification_is_rejected() {
+    let result = PersonalIdentification::new(DocumentType::CPF, "12345678900");
+
+    assert!(matches!(result, Err(ValidationError::Identification(_))));
+}
+
+#[test]
+fn access_token_is_redacted_from_debug() {
+    let builder = MercadoPagoSDKBuilder::new("APP_USR-558881221729581-091712");
+    assert!(!format!("{:?}", builder).contains("APP_USR"));
+
+    let sdk = builder.build();
+    assert!(!format!("{:?}", sdk).contains("APP_USR"));
+}
+
+#[tokio::test]
+async fn create_pix_payment() {
+    let stub = common::StubTransport::new().route(
+        Method::POST,
+        "/v1/payments",
+        201,
+        include_str!("assets/pix_payment_response.json"),
+    );
+    let sdk = common::create_stub_sdk(&stub);
+
+    let payer = PersonalIdentification::new(DocumentType::CPF, "19119119100").unwrap();
+    let payload = PixPayment::new(10.0, "Order #123", "buyer@example.com", payer)
+        .unwrap()
+        .build();
+
+    let payment = sdk
+        .create_payment(payload)
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+
+    assert_eq!(payment.status, PaymentStatus::Pending);
+    assert!(payment.pix_qr_code().is_some());
+    assert_eq!(
+        stub.requests()[0].body.as_ref().unwrap()["payment_method_id"],
+        "pix"
+    );
+}
+
+#[tokio::test]
+async fn authorize_through_custom_transport() {
+    let stub = common::StubTransport::new()
+        .route(
+            Method::POST,
+            "/oauth/token",
+            200,
+            r#"{"access_token": "APP_USR-123", "token_type": "Bearer", "expires_in": 15552000}"#,
+        )
+        .route(
+            Method::GET,
+            "/v1/payments/20359978",
+            200,
+            include_str!("assets/create_payment_response.json"),
+        );
+
+    let sdk = MercadoPagoSDKBuilder::with_credentials("123", "secret")
+        .set_base_url(common::STUB_BASE_URL)
+        .set_transport(stub.clone())
+        .authorize()
+        .await
+        .unwrap();
+    sdk.get_payment(20359978).unwrap().execute().await.unwrap();
+
+    let requests = stub.requests();
+    assert!(!requests[0].headers.contains_key("authorization"));
+    assert_eq!(
+        requests[0].body,
+        Some(serde_json::json!({
+            "client_id": "123",
+            "client_secret": "secret",
+            "grant_type": "client_credentials"
+        }))
+    );
+    assert_eq!(requests[1].headers["authorization"], "Bearer APP_USR-123");
+}
+
+#[tokio::test]
+async fn authorize_with_wrong_credentials() {
+    let stub = common::StubTransport::new().route(
+        Method::POST,
+        "/oauth/token",
+        400,
+        r#"{"message": "invalid client_id or client_secret", "error": "invalid_client", "status": 400, "cause": []}"#,
+    );
+
+    let err = MercadoPagoSDKBuilder::with_credentials("123", "wrong")
+        .set_base_url(common::STUB_BASE_URL)
+        .set_transport(stub.clone())
+        .authorize()
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, SDKError::CredentialsError(_)));
+}
+
+/// Records the names of the spans created, and the `attempts` recorded on them.
+#[cfg(feature = "tracing")]
+#[derive(Default, Clone)]
+struct SpanRecorder {
+    spans: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+    attempts: std::sync::Arc<std::sync::Mutex<Vec<u64>>>,
+}
+
+#[cfg(feature = "tracing")]
+impl tracing::field::Visit for SpanRecorder {
+    fn record_u64(&mut self, field: &tracing::field::Field, value: u64) {
+        if field.name() == "attempts" {
+            self.attempts.lock().unwrap().push(value);
+        }
+    }
+
+    fn record_debug(&mut self, _: &tracing::field::Field, _: &dyn std::fmt::Debug) {}
+}
+
+#[cfg(feature = "tracing")]
+impl tracing::Subscriber for SpanRecorder {
+    fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, attributes: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+        let mut spans = self.spans.lock().unwrap();
+        spans.push(attributes.metadata().name().to_string());
+        tracing::span::Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, _: &tracing::span::Id, values: &tracing::span::Record<'_>) {
+        values.record(&mut self.clone());
+    }
+
+    fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+    fn event(&self, _: &tracing::Event<'_>) {}
+    fn enter(&self, _: &tracing::span::Id) {}
+    fn exit(&self, _: &tracing::span::Id) {}
+}
+
+#[cfg(feature = "tracing")]
+#[tokio::test]
+async fn retried_requests_have_a_single_span() {
+    let stub = common::StubTransport::new()
+        .route(Method::GET, "/v1/payments/20359978", 502, "Bad Gateway")
+        .route(
+            Method::GET,
+            "/v1/payments/20359978",
+            200,
+            include_str!("assets/create_payment_response.json"),
+        );
+    let sdk = MercadoPagoSDKBuilder::new("TEST_ACCESS_TOKEN")
+        .set_base_url(common::STUB_BASE_URL)
+        .set_transport(stub.clone())
+        .set_retry_policy(RetryPolicy::new(3).set_base_delay(Duration::from_millis(1)))
+        .build();
+    let recorder = SpanRecorder::default();
+    let _guard = tracing::subscriber::set_default(recorder.clone());
+
+    sdk.get_payment(20359978).unwrap().execute().await.unwrap();
+
+    assert_eq!(*recorder.spans.lock().unwrap(), vec!["mercadopago.request"]);
+    assert_eq!(*recorder.attempts.lock().unwrap(), vec![1, 2]);
+}
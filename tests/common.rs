@@ -1,8 +1,129 @@
 /// ! Access token is the same used on official MercadoPago SDKs.
+use std::sync::{Arc, Mutex};
+
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use mercadopago_sdk_rust::errors::SDKError;
+use mercadopago_sdk_rust::transport::{HttpResponse, HttpTransport};
 use mercadopago_sdk_rust::{MercadoPagoSDK, MercadoPagoSDKBuilder};
+use reqwest::header::HeaderMap;
+use reqwest::{Method, Request, StatusCode};
+
+pub const STUB_BASE_URL: &str = "http://mercadopago.stub";
 
 pub fn create_sdk() -> MercadoPagoSDK {
     MercadoPagoSDKBuilder::with_token(
         "APP_USR-558881221729581-091712-44fdc612e60e3e638775d8b4003edd51-471763966",
     )
 }
+
+/// Creates an SDK which never leaves the process, answering requests from `stub`.
+pub fn create_stub_sdk(stub: &StubTransport) -> MercadoPagoSDK {
+    MercadoPagoSDKBuilder::new("TEST_ACCESS_TOKEN")
+        .set_base_url(STUB_BASE_URL)
+        .set_transport(stub.clone())
+        .build()
+}
+
+/// A request received by [`StubTransport`].
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: Method,
+    /// Path and query, without the base URL.
+    pub path: String,
+    pub headers: HeaderMap,
+    pub body: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone)]
+struct StubRoute {
+    method: Method,
+    path: String,
+    status: StatusCode,
+    body: String,
+}
+
+/// Answers requests with canned responses, and records every request it receives.
+#[derive(Debug, Default, Clone)]
+pub struct StubTransport {
+    routes: Arc<Mutex<Vec<StubRoute>>>,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl StubTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answers requests to `path` (without query string) with `status` and `body`.
+    ///
+    /// When many routes match the same request, they are answered in the order they were added,
+    /// with the last one answering every remaining request.
+    pub fn route(self, method: Method, path: &str, status: u16, body: &str) -> Self {
+        self.routes.lock().unwrap().push(StubRoute {
+            method,
+            path: path.to_string(),
+            status: StatusCode::from_u16(status).unwrap(),
+            body: body.to_string(),
+        });
+        self
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl HttpTransport for StubTransport {
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<HttpResponse, SDKError>> {
+        let url = request.url();
+        let path = match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        };
+
+        let recorded = RecordedRequest {
+            method: request.method().clone(),
+            path,
+            headers: request.headers().clone(),
+            body: request
+                .body()
+                .and_then(|b| b.as_bytes())
+                .map(|b| serde_json::from_slice(b).unwrap()),
+        };
+
+        let route = {
+            let mut routes = self.routes.lock().unwrap();
+            let matching = routes
+                .iter()
+                .enumerate()
+                .filter(|(_, r)| r.method == recorded.method && r.path == url.path())
+                .map(|(i, _)| i)
+                .collect::<Vec<_>>();
+
+            match matching.as_slice() {
+                [] => None,
+                [only] => Some(routes[*only].clone()),
+                [first, ..] => Some(routes.remove(*first)),
+            }
+        };
+        self.requests.lock().unwrap().push(recorded);
+
+        async move {
+            let route = route.unwrap_or(StubRoute {
+                method: Method::GET,
+                path: String::new(),
+                status: StatusCode::NOT_FOUND,
+                body: r#"{"message":"resource not found","error":"not_found","status":404,"cause":[]}"#
+                    .to_string(),
+            });
+
+            Ok(HttpResponse {
+                status: route.status,
+                headers: HeaderMap::new(),
+                body: route.body,
+            })
+        }
+        .boxed()
+    }
+}